@@ -1,4 +1,6 @@
 #![feature(repeat_generic_slice, async_await)]
+// Each buffer is written by index on purpose, since indexing is what the benchmarks compare
+#![allow(clippy::needless_range_loop)]
 /*
  * Copyright (c) 2019. The information/code/data contained within this file and all other files with the same copyright are protected under US Statutes. You must have explicit written access by Thomas P. Braun in order to access, view, modify, alter, or apply this code in any context commercial or non-commercial. If you have this code but were not given explicit written access by Thomas P. Braun, you must destroy the information herein for legal safety. You agree that if you apply the concepts herein without any written access, Thomas P. Braun will seek the maximum possible legal retribution.
  */
@@ -6,11 +8,10 @@
 #[macro_use]
 extern crate criterion;
 
-use bytes::{Buf, BufMut, BytesMut};
+use bytes::BytesMut;
 use criterion::{Criterion, ParameterizedBenchmark};
 
-use hyperbuf::hypervec::{HyperVec, WriteVisitor};
-use futures::executor::block_on;
+use hyperbuf::hypervec::HyperVec;
 
 fn vec(len: usize, slice: &[u8]) {
     let mut mem0 = Vec::with_capacity(len/slice.len());
@@ -27,7 +28,7 @@ fn vec(len: usize, slice: &[u8]) {
 }
 
 fn hyper_vec(len: usize, slice: &[u8]) {
-    let mut mem0 = HyperVec::new(len);
+    let mut mem0 = HyperVec::new_zeroed(len);
     let mem = &mut mem0;
 
     for idx in 0..(len/slice.len()) as isize {
@@ -0,0 +1,206 @@
+/*
+ * Copyright (c) 2019. The information/code/data contained within this file and all other files with the same copyright are protected under US Statutes. You must have explicit written access by Thomas P. Braun in order to access, view, modify, alter, or apply this code in any context commercial or non-commercial. If you have this code but were not given explicit written access by Thomas P. Braun, you must destroy the information herein for legal safety. You agree that if you apply the concepts herein without any written access, Thomas P. Braun will seek the maximum possible legal retribution.
+ */
+
+use std::fmt::{Debug, Display, Formatter};
+
+use crate::pod::HyperPod;
+
+// Items shared by every endian type: encoding, decoding, conversions and arithmetic with the primitive. `$encode` turns
+// `$value` into its bytes, and `$decode` turns `$bytes` back into a value
+macro_rules! endian_common {
+    ($name:ident, $prim:ty, $size:expr, |$value:ident| $encode:expr, |$bytes:ident| $decode:expr) => {
+        // A byte array has neither padding nor invalid bit patterns
+        unsafe impl HyperPod for $name {}
+
+        impl $name {
+            /// Encodes `value`
+            #[inline]
+            pub fn new($value: $prim) -> Self {
+                $name($encode)
+            }
+
+            /// Decodes the value
+            #[inline]
+            pub fn get(&self) -> $prim {
+                let $bytes = self.0;
+                $decode
+            }
+
+            /// Replaces the value
+            #[inline]
+            pub fn set(&mut self, $value: $prim) {
+                self.0 = $encode;
+            }
+
+            /// Wraps bytes which are already encoded
+            #[inline]
+            pub fn from_bytes(bytes: [u8; $size]) -> Self {
+                $name(bytes)
+            }
+
+            /// Returns the encoded bytes
+            #[inline]
+            pub fn to_bytes(&self) -> [u8; $size] {
+                self.0
+            }
+        }
+
+        impl From<$prim> for $name {
+            fn from(value: $prim) -> Self {
+                $name::new(value)
+            }
+        }
+
+        impl From<$name> for $prim {
+            fn from(value: $name) -> Self {
+                value.get()
+            }
+        }
+
+        impl Debug for $name {
+            fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+                write!(f, "{}({:?})", stringify!($name), self.get())
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+                Display::fmt(&self.get(), f)
+            }
+        }
+
+        // Overflow behaves as it does for the primitive; integers also offer wrapping_* and checked_*
+        impl std::ops::Add<$prim> for $name {
+            type Output = Self;
+
+            fn add(self, rhs: $prim) -> Self {
+                Self::new(self.get() + rhs)
+            }
+        }
+
+        impl std::ops::Sub<$prim> for $name {
+            type Output = Self;
+
+            fn sub(self, rhs: $prim) -> Self {
+                Self::new(self.get() - rhs)
+            }
+        }
+    };
+}
+
+macro_rules! endian_int {
+    ($(#[$attr:meta])* $name:ident, $prim:ty, $size:expr, $to:ident, $from:ident) => {
+        $(#[$attr])*
+        #[repr(transparent)]
+        #[derive(Copy, Clone, Default, PartialEq, Eq, Hash)]
+        pub struct $name([u8; $size]);
+
+        endian_common!($name, $prim, $size, |value| value.$to(), |bytes| <$prim>::$from(bytes));
+
+        impl $name {
+            /// Adds `rhs`, wrapping around at the bounds of the type
+            #[inline]
+            pub fn wrapping_add(self, rhs: $prim) -> Self {
+                Self::new(self.get().wrapping_add(rhs))
+            }
+
+            /// Subtracts `rhs`, wrapping around at the bounds of the type
+            #[inline]
+            pub fn wrapping_sub(self, rhs: $prim) -> Self {
+                Self::new(self.get().wrapping_sub(rhs))
+            }
+
+            /// Adds `rhs`, returning None if the sum overflows
+            #[inline]
+            pub fn checked_add(self, rhs: $prim) -> Option<Self> {
+                self.get().checked_add(rhs).map(Self::new)
+            }
+
+            /// Subtracts `rhs`, returning None if the difference overflows
+            #[inline]
+            pub fn checked_sub(self, rhs: $prim) -> Option<Self> {
+                self.get().checked_sub(rhs).map(Self::new)
+            }
+        }
+    };
+}
+
+macro_rules! endian_float {
+    ($(#[$attr:meta])* $name:ident, $prim:ty, $bits:ty, $size:expr, $to:ident, $from:ident) => {
+        $(#[$attr])*
+        #[repr(transparent)]
+        #[derive(Copy, Clone, Default)]
+        pub struct $name([u8; $size]);
+
+        endian_common!($name, $prim, $size, |value| value.to_bits().$to(), |bytes| <$prim>::from_bits(<$bits>::$from(bytes)));
+
+        // Compared by value rather than by bytes, so that NaN is unequal to itself and 0.0 equals -0.0
+        impl PartialEq for $name {
+            fn eq(&self, other: &Self) -> bool {
+                self.get() == other.get()
+            }
+        }
+    };
+}
+
+endian_int!(
+    /// A `u16` stored big-endian, with an alignment of 1
+    U16Be, u16, 2, to_be_bytes, from_be_bytes);
+endian_int!(
+    /// A `u16` stored little-endian, with an alignment of 1
+    U16Le, u16, 2, to_le_bytes, from_le_bytes);
+endian_int!(
+    /// A `u32` stored big-endian, with an alignment of 1
+    U32Be, u32, 4, to_be_bytes, from_be_bytes);
+endian_int!(
+    /// A `u32` stored little-endian, with an alignment of 1
+    U32Le, u32, 4, to_le_bytes, from_le_bytes);
+endian_int!(
+    /// A `u64` stored big-endian, with an alignment of 1
+    U64Be, u64, 8, to_be_bytes, from_be_bytes);
+endian_int!(
+    /// A `u64` stored little-endian, with an alignment of 1
+    U64Le, u64, 8, to_le_bytes, from_le_bytes);
+endian_int!(
+    /// A `u128` stored big-endian, with an alignment of 1
+    U128Be, u128, 16, to_be_bytes, from_be_bytes);
+endian_int!(
+    /// A `u128` stored little-endian, with an alignment of 1
+    U128Le, u128, 16, to_le_bytes, from_le_bytes);
+endian_int!(
+    /// An `i16` stored big-endian, with an alignment of 1
+    I16Be, i16, 2, to_be_bytes, from_be_bytes);
+endian_int!(
+    /// An `i16` stored little-endian, with an alignment of 1
+    I16Le, i16, 2, to_le_bytes, from_le_bytes);
+endian_int!(
+    /// An `i32` stored big-endian, with an alignment of 1
+    I32Be, i32, 4, to_be_bytes, from_be_bytes);
+endian_int!(
+    /// An `i32` stored little-endian, with an alignment of 1
+    I32Le, i32, 4, to_le_bytes, from_le_bytes);
+endian_int!(
+    /// An `i64` stored big-endian, with an alignment of 1
+    I64Be, i64, 8, to_be_bytes, from_be_bytes);
+endian_int!(
+    /// An `i64` stored little-endian, with an alignment of 1
+    I64Le, i64, 8, to_le_bytes, from_le_bytes);
+endian_int!(
+    /// An `i128` stored big-endian, with an alignment of 1
+    I128Be, i128, 16, to_be_bytes, from_be_bytes);
+endian_int!(
+    /// An `i128` stored little-endian, with an alignment of 1
+    I128Le, i128, 16, to_le_bytes, from_le_bytes);
+endian_float!(
+    /// An `f32` stored big-endian, with an alignment of 1
+    F32Be, f32, u32, 4, to_be_bytes, from_be_bytes);
+endian_float!(
+    /// An `f32` stored little-endian, with an alignment of 1
+    F32Le, f32, u32, 4, to_le_bytes, from_le_bytes);
+endian_float!(
+    /// An `f64` stored big-endian, with an alignment of 1
+    F64Be, f64, u64, 8, to_be_bytes, from_be_bytes);
+endian_float!(
+    /// An `f64` stored little-endian, with an alignment of 1
+    F64Le, f64, u64, 8, to_le_bytes, from_le_bytes);
@@ -0,0 +1,98 @@
+/*
+ * Copyright (c) 2019. The information/code/data contained within this file and all other files with the same copyright are protected under US Statutes. You must have explicit written access by Thomas P. Braun in order to access, view, modify, alter, or apply this code in any context commercial or non-commercial. If you have this code but were not given explicit written access by Thomas P. Braun, you must destroy the information herein for legal safety. You agree that if you apply the concepts herein without any written access, Thomas P. Braun will seek the maximum possible legal retribution.
+ */
+
+use std::ops::{Deref, DerefMut};
+use std::time::Duration;
+
+use crate::hypervec::{HyperVec, ReadVisitor, WriteVisitor};
+use crate::impls::Castable;
+use crate::pod::HyperPod;
+use crate::results::InformationResult;
+use crate::scheduler::wait_for_admission;
+
+/// Exclusive access to the bytes of a [HyperVec] as a `T`. The write ticket is released when the guard drops
+pub struct WriteGuard<'visit, T> {
+    pub(crate) visitor: WriteVisitor<'visit, T>
+}
+
+/// Shared access to the bytes of a [HyperVec] as a `T`. The read ticket is released when the guard drops
+pub struct ReadGuard<'visit, T> {
+    pub(crate) visitor: ReadVisitor<'visit, T>
+}
+
+impl HyperVec {
+    /// Draws a write ticket when first polled, then resolves once every visitor ahead of it has been released. Unlike
+    /// [WriteVisitor::visit], the returned guard may be held across other awaits. Named apart from [std::io::Write::write],
+    /// which HyperVec also implements
+    pub async fn write_guard<T: HyperPod>(&mut self) -> InformationResult<'_, WriteGuard<'_, T>> {
+        let visitor = self.cast_mut::<T>()?;
+        (&visitor).await?;
+        visitor.check_fit()?;
+        Ok(WriteGuard { visitor })
+    }
+
+    /// Draws a read ticket when first polled, then resolves once every writer ahead of it has been released. Readers
+    /// in the same epoch hold their guards concurrently. Named apart from [std::io::Read::read], which HyperVec also implements
+    pub async fn read_guard<T: HyperPod>(&self) -> InformationResult<'_, ReadGuard<'_, T>> {
+        let visitor = self.cast::<T>()?;
+        (&visitor).await?;
+        visitor.check_fit()?;
+        Ok(ReadGuard { visitor })
+    }
+
+    /// Parks the calling thread until a write ticket is admitted, then returns its guard
+    pub fn write_blocking<T: HyperPod>(&mut self) -> InformationResult<'_, WriteGuard<'_, T>> {
+        let visitor = self.cast_mut::<T>()?;
+        unsafe { wait_for_admission(&*visitor.ptr, &visitor.position, None) }?;
+        visitor.check_fit()?;
+        Ok(WriteGuard { visitor })
+    }
+
+    /// Like [HyperVec::write_blocking], but gives up once `timeout` elapses, returning [MemError::TIMED_OUT]
+    pub fn write_blocking_timeout<T: HyperPod>(&mut self, timeout: Duration) -> InformationResult<'_, WriteGuard<'_, T>> {
+        let visitor = self.cast_mut::<T>()?;
+        unsafe { wait_for_admission(&*visitor.ptr, &visitor.position, Some(timeout)) }?;
+        visitor.check_fit()?;
+        Ok(WriteGuard { visitor })
+    }
+
+    /// Parks the calling thread until a read ticket is admitted, then returns its guard
+    pub fn read_blocking<T: HyperPod>(&self) -> InformationResult<'_, ReadGuard<'_, T>> {
+        let visitor = self.cast::<T>()?;
+        wait_for_admission(self, &visitor.position, None)?;
+        visitor.check_fit()?;
+        Ok(ReadGuard { visitor })
+    }
+
+    /// Like [HyperVec::read_blocking], but gives up once `timeout` elapses, returning [MemError::TIMED_OUT]
+    pub fn read_blocking_timeout<T: HyperPod>(&self, timeout: Duration) -> InformationResult<'_, ReadGuard<'_, T>> {
+        let visitor = self.cast::<T>()?;
+        wait_for_admission(self, &visitor.position, Some(timeout))?;
+        visitor.check_fit()?;
+        Ok(ReadGuard { visitor })
+    }
+}
+
+impl<'visit, T> Deref for WriteGuard<'visit, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*self.visitor.object_ptr() }
+    }
+}
+
+impl<'visit, T> DerefMut for WriteGuard<'visit, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.visitor.begin_write();
+        unsafe { &mut *self.visitor.object_ptr() }
+    }
+}
+
+impl<'visit, T> Deref for ReadGuard<'visit, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { self.visitor.get_unchecked() }
+    }
+}
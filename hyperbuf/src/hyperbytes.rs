@@ -0,0 +1,139 @@
+/*
+ * Copyright (c) 2019. The information/code/data contained within this file and all other files with the same copyright are protected under US Statutes. You must have explicit written access by Thomas P. Braun in order to access, view, modify, alter, or apply this code in any context commercial or non-commercial. If you have this code but were not given explicit written access by Thomas P. Braun, you must destroy the information herein for legal safety. You agree that if you apply the concepts herein without any written access, Thomas P. Braun will seek the maximum possible legal retribution.
+ */
+
+use std::alloc::Layout;
+use std::borrow::Borrow;
+use std::fmt::{Debug, Formatter, Error};
+use std::hash::{Hash, Hasher};
+use std::ops::{Deref, RangeBounds};
+use std::sync::Arc;
+
+use crate::hypervec::HyperVec;
+use crate::impls::ByteWrapper;
+
+/// Owns an allocation on behalf of every [HyperVec] and [HyperBytes] that views into it. The allocation is released
+/// once the last view drops
+pub(crate) struct SharedStorage {
+    ptr: *mut u8,
+    layout: Layout
+}
+
+impl SharedStorage {
+    /// Takes ownership of the allocation at `ptr`, which must have been allocated with `layout`
+    pub(crate) fn new(ptr: *mut u8, layout: Layout) -> Self {
+        Self { ptr, layout }
+    }
+}
+
+impl Drop for SharedStorage {
+    fn drop(&mut self) {
+        if self.layout.size() != 0 {
+            unsafe { std::alloc::dealloc(self.ptr, self.layout) }
+        }
+    }
+}
+
+/// The allocation is never written through the storage itself; the views coordinate access to their (disjoint) regions
+unsafe impl Send for SharedStorage {}
+unsafe impl Sync for SharedStorage {}
+
+/// An immutable view into bytes that were once owned by a [HyperVec] (see [HyperVec::freeze]). Cloning only bumps a reference
+/// count, so a decoded frame may be handed to several tasks without copying
+pub struct HyperBytes {
+    storage: Arc<SharedStorage>,
+    ptr: *const u8,
+    len: usize
+}
+
+/// The bytes are never mutated after freezing, so sharing between threads is safe
+unsafe impl Send for HyperBytes {}
+unsafe impl Sync for HyperBytes {}
+
+impl HyperBytes {
+    /// Assembles a view over `len` bytes starting at `ptr` within the shared allocation
+    pub(crate) fn from_shared(storage: Arc<SharedStorage>, ptr: *const u8, len: usize) -> Self {
+        Self { storage, ptr, len }
+    }
+
+    /// Returns the bytes
+    #[inline]
+    pub fn as_slice(&self) -> &[u8] {
+        unsafe { &*std::ptr::slice_from_raw_parts(self.ptr, self.len) }
+    }
+
+    /// Returns a HyperBytes over the subset `range` of self. No bytes are copied
+    pub fn slice<R: RangeBounds<usize>>(&self, range: R) -> HyperBytes {
+        let (start, end) = crate::util::resolve_range(&range, self.len);
+        Self::from_shared(self.storage.clone(), unsafe { self.ptr.add(start) }, end - start)
+    }
+
+    /// Splits the bytes in two at `at`. Afterwards, self contains [at, len) and the returned HyperBytes contains [0, at)
+    pub fn split_to(&mut self, at: usize) -> HyperBytes {
+        let head = self.slice(..at);
+        self.ptr = unsafe { self.ptr.add(at) };
+        self.len -= at;
+        head
+    }
+
+    /// Splits the bytes in two at `at`. Afterwards, self contains [0, at) and the returned HyperBytes contains [at, len)
+    pub fn split_off(&mut self, at: usize) -> HyperBytes {
+        let tail = self.slice(at..);
+        self.len = at;
+        tail
+    }
+
+    /// Copies the bytes into a fresh, mutable [HyperVec]
+    pub fn to_hypervec(&self) -> HyperVec {
+        HyperVec::wrap_bytes(self.as_slice())
+    }
+}
+
+impl Clone for HyperBytes {
+    fn clone(&self) -> Self {
+        Self::from_shared(self.storage.clone(), self.ptr, self.len)
+    }
+}
+
+impl Deref for HyperBytes {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl AsRef<[u8]> for HyperBytes {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl Borrow<[u8]> for HyperBytes {
+    #[inline]
+    fn borrow(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl PartialEq for HyperBytes {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl Eq for HyperBytes {}
+
+impl Hash for HyperBytes {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state)
+    }
+}
+
+impl Debug for HyperBytes {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "HyperBytes({:?})", self.as_slice())
+    }
+}
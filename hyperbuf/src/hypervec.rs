@@ -5,14 +5,26 @@
 use std::alloc::Layout;
 use std::future::Future;
 use std::marker::PhantomData;
+use std::ops::{Bound, Range, RangeBounds};
 use std::pin::Pin;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::vec::IntoIter;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::task::{Context, Poll};
 use bytes::BufMut;
 use crate::results::{InformationResult, MemError};
 use crate::impls::*;
 use crate::partition_map::PartitionMap;
+use crate::hyperbytes::{HyperBytes, SharedStorage};
+use crate::iter::TypedIter;
+use crate::pod::HyperPod;
+use crate::scheduler::{wait_for_admission, AdmissionTimeout, Place, Position, Scheduler, Ticket, TicketKind};
+pub use crate::scheduler::{SchedulingPolicy, MAX_PRIORITY_LANES};
+use std::time::Duration;
+use std::cell::Cell;
 use std::fmt::{Display, Formatter, Error};
+#[cfg(feature = "stats")]
+use crate::stats::{HyperVecStats, StatsRecorder};
 
 /// This is a type which can be re-interpreted to any type, regardless of alignment
 #[fundamental]
@@ -20,60 +32,164 @@ use std::fmt::{Display, Formatter, Error};
 pub struct HyperVec {
     /// #
     pub ptr: *mut u8,
+    /// The number of initialized bytes. This is never greater than the capacity described by `layout`
     pub(crate) len: usize,
     pub(crate) cursor: isize,
     /// The read and write versions are only for editing data through visitors
     pub(crate) read_version: AtomicUsize,
     pub(crate) write_version: AtomicUsize,
+    /// Issues the tickets whose releases `read_version` and `write_version` count
+    pub(crate) scheduler: Scheduler,
+    /// The low bits count the WriteVisitors currently writing, and the high bits how many have finished, so that
+    /// [HyperVec::read_consistent] can detect torn reads even while disjoint range writers run at once
+    pub(crate) sequence: AtomicU64,
+    /// The number of [HyperVec::read_consistent] calls copying out of the allocation. A reallocation waits for them to finish
+    /// before it frees the bytes they may be reading
+    pub(crate) pinned: AtomicUsize,
     /// See [WriteVisitor] for the definition of "corrupt"
     pub(crate) corrupt: bool,
+    /// Counts what happens to the buffer, see [HyperVec::stats]
+    #[cfg(feature = "stats")]
+    pub(crate) stats: StatsRecorder,
     pub(crate) endianness: Endianness,
     pub(crate) partition_map: Option<PartitionMap>,
+    /// Present when the bytes are a view into an allocation shared with other HyperVecs or [HyperBytes] (see [HyperVec::split_off]).
+    /// A view never frees nor reallocates the shared allocation; it moves into an allocation of its own once it must grow
+    pub(crate) storage: Option<Arc<SharedStorage>>,
     /// We place the layout at the end of the struct to ensure that, in the event of corruption, the bytes do not interfere with this struct.
+    /// The layout describes the entire allocation (or, for views, the portion of it which may be used); its size is the capacity of the HyperVec
     pub(crate) layout: Layout
 }
 
+/// The smallest non-zero capacity that amortized growth will allocate
+const MIN_NON_ZERO_CAPACITY: usize = 8;
+
 impl HyperVec {
     #[inline]
-    /// Returns a HyperVec module that is blocked
-    pub fn new(len: usize) -> Self {
-        let layout = Layout::array::<u8>(len).unwrap();
-        let ptr = unsafe { std::alloc::alloc(layout) };
-        Self { ptr, len, layout, cursor: 0, read_version: AtomicUsize::new(0), write_version: AtomicUsize::new(0), corrupt: false, endianness: Endianness::target(), partition_map: None}
+    /// Returns an empty HyperVec module that is blocked, with room for `capacity` bytes. Since none of the bytes are initialized,
+    /// the length is zero; use [HyperVec::new_zeroed] for a buffer which starts out `len` bytes long
+    pub fn new(capacity: usize) -> Self {
+        Self::with_capacity(capacity)
     }
 
     #[inline]
-    /// Returns a HyperVec module that is blocked
+    /// Returns a HyperVec module that is blocked, holding `len` zeroed bytes
     pub fn new_zeroed(len: usize) -> Self {
         let layout = Layout::array::<u8>(len).unwrap();
-        let ptr = unsafe { std::alloc::alloc_zeroed(layout) };
-        Self { ptr, len, layout, cursor: 0, read_version: AtomicUsize::new(0), write_version: AtomicUsize::new(0), corrupt: false, endianness: Endianness::target(), partition_map: None}
+        let ptr = allocate(layout, true);
+        Self::from_raw_parts(ptr, len, layout)
+    }
+
+    #[inline]
+    /// Returns an empty HyperVec that can hold at least `capacity` bytes before reallocating
+    pub fn with_capacity(capacity: usize) -> Self {
+        let layout = Layout::array::<u8>(capacity).unwrap();
+        let ptr = allocate(layout, false);
+        Self::from_raw_parts(ptr, 0, layout)
     }
 
     #[inline]
     /// Wraps around a pre-existing value, translating it into its bytes.
-    /// Use wrap_bytes for arrays; this is more for structs
-    pub fn wrap<T: ?Sized>(t: &T) -> Self {
+    /// Use wrap_bytes for arrays; this is more for structs. Only [HyperPod] values may be wrapped, since the padding of any
+    /// other type would leave uninitialized bytes within the buffer
+    pub fn wrap<T: HyperPod>(t: &T) -> Self {
         let ptr0 = t as *const T as *const u8;
-        println!("[WRAP] {} {}", std::mem::size_of_val(t), std::mem::align_of_val(t));
         let layout = Layout::for_value::<T>(t);
-        let ptr = unsafe { std::alloc::alloc(layout) };
-
-        println!("LAYOUT size: {}", layout.size());
+        let ptr = allocate(layout, false);
 
         unsafe { std::ptr::copy_nonoverlapping(ptr0, ptr, layout.size()) };
 
+        Self::from_raw_parts(ptr, layout.size(), layout)
+    }
+
+    /// Assembles a HyperVec around an allocation described by `layout`, of which the first `len` bytes are in use.
+    /// The HyperVec takes ownership of the allocation
+    #[inline]
+    pub(crate) fn from_raw_parts(ptr: *mut u8, len: usize, layout: Layout) -> Self {
+        debug_assert!(len <= layout.size());
         Self {
             ptr,
-            len: layout.size(),
+            len,
             cursor: 0,
             read_version: AtomicUsize::new(0),
             write_version: AtomicUsize::new(0),
+            scheduler: Scheduler::new(SchedulingPolicy::Fifo),
+            sequence: AtomicU64::new(0),
+            pinned: AtomicUsize::new(0),
             corrupt: false,
-            layout,
+            #[cfg(feature = "stats")]
+            stats: StatsRecorder::default(),
             endianness: Endianness::target(),
-            partition_map: None
+            partition_map: None,
+            storage: None,
+            layout
+        }
+    }
+
+    /// Assembles a HyperVec which views `capacity` bytes starting at `ptr` within a shared allocation
+    #[inline]
+    pub(crate) fn from_shared(storage: Arc<SharedStorage>, ptr: *mut u8, len: usize, capacity: usize) -> Self {
+        let mut hvec = Self::from_raw_parts(ptr, len, Layout::array::<u8>(capacity).unwrap());
+        hvec.storage = Some(storage);
+        hvec
+    }
+
+    /// Splits the buffer in two at `at`. Afterwards, self contains the bytes [0, at) and the returned HyperVec contains [at, len).
+    /// No bytes are copied; both halves point into the same allocation, which is freed once neither half (nor any [HyperBytes]
+    /// frozen from them) uses it. The cursor is divided between the halves, while objects tracked by the partition map stay with self
+    pub fn split_off(&mut self, at: usize) -> HyperVec {
+        assert!(at <= self.len, "split_off index {} exceeds the length {}", at, self.len);
+        let storage = self.share();
+        let tail_ptr = unsafe { self.ptr.add(at) };
+        let mut tail = Self::from_shared(storage, tail_ptr, self.len - at, self.capacity() - at);
+        tail.endianness = self.endianness;
+        tail.cursor = std::cmp::max(self.cursor - at as isize, 0);
+
+        self.cursor = std::cmp::min(self.cursor, at as isize);
+        if let Some(map) = self.partition_map.as_mut() {
+            map.adjust_for_edit(at as isize, (self.len - at) as isize, 0);
+        }
+        self.len = at;
+        self.layout = Layout::array::<u8>(at).unwrap();
+        tail
+    }
+
+    /// Splits the buffer in two at `at`. Afterwards, self contains the bytes [at, len) and the returned HyperVec contains [0, at).
+    /// This is the typical way to detach a decoded frame from the front of a receive buffer. Like [split_off](HyperVec::split_off),
+    /// no bytes are copied. Objects tracked by the partition map stay with self
+    pub fn split_to(&mut self, at: usize) -> HyperVec {
+        assert!(at <= self.len, "split_to index {} exceeds the length {}", at, self.len);
+        let storage = self.share();
+        let mut head = Self::from_shared(storage, self.ptr, at, at);
+        head.endianness = self.endianness;
+        head.cursor = std::cmp::min(self.cursor, at as isize);
+
+        self.cursor = std::cmp::max(self.cursor - at as isize, 0);
+        if let Some(map) = self.partition_map.as_mut() {
+            map.adjust_for_edit(0, at as isize, 0);
+        }
+        self.ptr = unsafe { self.ptr.add(at) };
+        self.len -= at;
+        self.layout = Layout::array::<u8>(self.capacity() - at).unwrap();
+        head
+    }
+
+    /// Converts the HyperVec into an immutable, reference-counted [HyperBytes] without copying. The result is cheaply
+    /// clonable and may be sent to (and shared between) other threads
+    pub fn freeze(mut self) -> HyperBytes {
+        let storage = self.share();
+        HyperBytes::from_shared(storage, self.ptr, self.len)
+    }
+
+    /// Hands the allocation over to a reference-counted [SharedStorage] (if this has not occured already), so that other views may point into it
+    fn share(&mut self) -> Arc<SharedStorage> {
+        if let Some(storage) = self.storage.as_ref() {
+            return storage.clone();
         }
+
+        let storage = Arc::new(SharedStorage::new(self.ptr, self.layout));
+        self.storage = Some(storage.clone());
+        storage
     }
 
     /// Debug ONLY
@@ -107,8 +223,180 @@ impl HyperVec {
         self.len
     }
 
+    /// Returns the number of bytes the HyperVec can hold without reallocating
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.layout.size()
+    }
+
+    /// Reserves capacity for at least `additional` more bytes beyond the current length. The capacity
+    /// grows geometrically, so repeated calls with small amounts only reallocate a logarithmic number of times
+    pub fn reserve(&mut self, additional: usize) {
+        let required = self.len.checked_add(additional).expect("capacity overflow");
+        if required <= self.capacity() {
+            return;
+        }
+
+        let doubled = self.capacity().saturating_mul(2);
+        let new_capacity = std::cmp::max(std::cmp::max(doubled, required), MIN_NON_ZERO_CAPACITY);
+        self.reallocate(new_capacity);
+    }
+
+    /// Reserves capacity for exactly `additional` more bytes beyond the current length. Prefer [reserve](HyperVec::reserve)
+    /// if further growth is expected
+    pub fn reserve_exact(&mut self, additional: usize) {
+        let required = self.len.checked_add(additional).expect("capacity overflow");
+        if required > self.capacity() {
+            self.reallocate(required);
+        }
+    }
+
+    /// Shrinks the capacity down to the length, releasing the unused portion of the allocation
+    pub fn shrink_to_fit(&mut self) {
+        if self.capacity() > self.len {
+            self.reallocate(self.len);
+        }
+    }
+
+    /// Moves the buffer into an allocation of exactly `new_capacity` bytes. The alignment of the original allocation is preserved.
+    /// The caller must ensure that `new_capacity` is not less than the length
+    fn reallocate(&mut self, new_capacity: usize) {
+        self.reallocate_aligned(new_capacity, self.layout.align())
+    }
+
+    /// Like [extend](HyperVec::extend), but first moves the buffer into an allocation aligned to `align` if the bytes are not
+    /// aligned to it, or if growing would reallocate them with a lesser alignment
+    pub(crate) fn extend_aligned(&mut self, additional_bytes: usize, align: usize) {
+        let required = self.len.checked_add(additional_bytes).expect("capacity overflow");
+        let misaligned = self.ptr as usize % align != 0;
+        if misaligned || (required > self.capacity() && self.layout.align() < align) {
+            self.reallocate_aligned(std::cmp::max(required, self.capacity()), std::cmp::max(align, self.layout.align()));
+        }
+
+        self.extend(additional_bytes);
+    }
+
+    /// Moves the buffer into an allocation of exactly `new_capacity` bytes, aligned to `align`
+    fn reallocate_aligned(&mut self, new_capacity: usize, align: usize) {
+        debug_assert!(new_capacity >= self.len);
+        #[cfg(feature = "stats")]
+        self.stats.reallocated();
+
+        // The move counts as a write, so that read_consistent never trusts a pointer or length read across it. The old bytes
+        // are only freed once the copies already underway are done with them
+        self.begin_sequence();
+        std::sync::atomic::fence(Ordering::SeqCst);
+        while self.pinned.load(Ordering::SeqCst) != 0 {
+            std::sync::atomic::spin_loop_hint();
+        }
+
+        if self.storage.is_some() {
+            self.reallocate_shared(new_capacity, align);
+        } else {
+            self.reallocate_owned(new_capacity, align);
+        }
+        self.end_sequence();
+    }
+
+    /// Grows or shrinks the allocation this HyperVec owns in place where the allocator allows
+    fn reallocate_owned(&mut self, new_capacity: usize, align: usize) {
+        let new_layout = Layout::from_size_align(new_capacity, align).expect("capacity overflow");
+
+        let ptr = if self.layout.size() == 0 {
+            allocate(new_layout, false)
+        } else if new_capacity == 0 || align != self.layout.align() {
+            // realloc cannot change the alignment, so the bytes are moved by hand
+            let ptr = allocate(new_layout, false);
+            unsafe {
+                std::ptr::copy_nonoverlapping(self.ptr, ptr, self.len);
+                std::alloc::dealloc(self.ptr, self.layout);
+            }
+            ptr
+        } else {
+            let ptr = unsafe { std::alloc::realloc(self.ptr, self.layout, new_capacity) };
+            if ptr.is_null() {
+                std::alloc::handle_alloc_error(new_layout)
+            }
+            ptr
+        };
+
+        self.ptr = ptr;
+        self.layout = new_layout;
+    }
+
+    /// A view never reallocates the shared allocation; instead, its bytes are copied into an allocation of its own
+    fn reallocate_shared(&mut self, new_capacity: usize, align: usize) {
+        let new_layout = Layout::from_size_align(new_capacity, align).expect("capacity overflow");
+        let ptr = allocate(new_layout, false);
+        unsafe { std::ptr::copy_nonoverlapping(self.ptr, ptr, self.len) };
+
+        self.ptr = ptr;
+        self.layout = new_layout;
+        self.storage = None;
+    }
+
+    /// Makes room for `amt` bytes at the cursor, growing the capacity if necessary. If the cursor sits beyond the length, the gap is zeroed
+    /// so that every byte below the length remains initialized
+    pub(crate) fn prepare_write_at_cursor(&mut self, amt: usize) {
+        let cursor = std::cmp::max(self.cursor, 0) as usize;
+        let end = cursor.checked_add(amt).expect("capacity overflow");
+        if end > self.len {
+            self.reserve(end - self.len);
+        }
+
+        if cursor > self.len {
+            unsafe { std::ptr::write_bytes(self.ptr.add(self.len), 0, cursor - self.len) };
+            self.len = cursor;
+        }
+    }
+
+    /// Returns an immutable slice of the initialized bytes. Since the slice borrows the HyperVec, no [WriteVisitor] can be
+    /// issued while it is alive. Reserved capacity is never part of the slice, as every constructor and every operation which
+    /// grows the length initializes the bytes it adds
+    #[inline]
+    pub fn as_slice(&self) -> &[u8] {
+        unsafe { &*std::ptr::slice_from_raw_parts(self.ptr, self.len) }
+    }
+
+    /// Returns a mutable slice of the initialized bytes. Since the slice mutably borrows the HyperVec, no visitors can be
+    /// issued while it is alive
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        unsafe { &mut *std::ptr::slice_from_raw_parts_mut(self.ptr, self.len) }
+    }
+
+    /// Returns an iterator over the initialized bytes. Unlike consuming the HyperVec, this leaves the cursor untouched
+    #[inline]
+    pub fn iter(&self) -> std::slice::Iter<u8> {
+        self.as_slice().iter()
+    }
+
+    /// Returns an iterator that allows modifying each initialized byte
+    #[inline]
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<u8> {
+        self.as_mut_slice().iter_mut()
+    }
+
+    /// Returns an iterator over `chunk_size` bytes at a time. The last chunk may be shorter
+    #[inline]
+    pub fn chunks(&self, chunk_size: usize) -> std::slice::Chunks<u8> {
+        self.as_slice().chunks(chunk_size)
+    }
+
+    /// Returns an iterator over exactly `chunk_size` bytes at a time. Trailing bytes that do not fill a chunk are skipped
+    #[inline]
+    pub fn chunks_exact(&self, chunk_size: usize) -> std::slice::ChunksExact<u8> {
+        self.as_slice().chunks_exact(chunk_size)
+    }
+
+    /// Returns an iterator which decodes consecutive values of type `T` from the bytes, using the buffer's [Endianness]
+    #[inline]
+    pub fn iter_as<T: EndianPrimitive>(&self) -> TypedIter<T> {
+        TypedIter::new(self.as_slice(), self.endianness)
+    }
+
     /// Return an immutable slice of the underlying bytes
-    pub unsafe fn bytes(&self) -> &[u8] {
+    pub unsafe fn get_full_bytes(&self) -> &[u8] {
         &*std::ptr::slice_from_raw_parts(self.ptr, self.len)
     }
 
@@ -122,9 +410,9 @@ impl HyperVec {
         &mut *std::ptr::slice_from_raw_parts_mut(self.ptr.offset(self.cursor), self.remaining_mut())
     }
 
-    /// Returns the bytes between the cursor position and the remaining mutable bytes on the heap
+    /// Returns the initialized bytes between the cursor position and the length
     pub unsafe fn get_bytes_cursor(&mut self) -> &[u8] {
-        &*std::ptr::slice_from_raw_parts(self.ptr.offset(self.cursor), self.remaining_mut())
+        &*std::ptr::slice_from_raw_parts(self.ptr.offset(self.cursor), self.len.saturating_sub(self.cursor as usize))
     }
 
     /// Reads the cursor position
@@ -132,19 +420,114 @@ impl HyperVec {
         self.cursor
     }
 
-    /// Reads the value at the current cursor
+    /// Reads the value at the current cursor. Panics if the cursor is out of bounds
     pub fn read_cursor(&self) -> u8 {
-        unsafe { *self.ptr.offset(self.cursor) }
+        self[self.cursor]
     }
 
-    /// Reads the value at the supplied index which is offset from the intiial pointer
+    /// Reads the value at the supplied index which is offset from the intiial pointer. Panics if `pos` is out of bounds
     pub fn read_relative(&self, pos: isize) -> u8 {
-        unsafe { *self.ptr.offset(pos) }
+        self[pos]
     }
 
-    /// Reads the value at the supplied index which is offset from the cursor position
+    /// Reads the value at the supplied index which is offset from the cursor position. Panics if the resulting index is out of bounds
     pub fn read_cursor_offset(&self, pos: isize) -> u8 {
-        unsafe { *self.ptr.offset(self.cursor + pos) }
+        self[self.cursor + pos]
+    }
+
+    /// Reads the value at the supplied index which is offset from the intiial pointer without checking the bounds
+    pub unsafe fn read_relative_unchecked(&self, pos: isize) -> u8 {
+        *self.ptr.offset(pos)
+    }
+
+    /// Reads the value at the supplied index which is offset from the cursor position without checking the bounds
+    pub unsafe fn read_cursor_offset_unchecked(&self, pos: isize) -> u8 {
+        *self.ptr.offset(self.cursor + pos)
+    }
+
+    /// Returns a reference to the byte at `index`, or None if `index` is out of bounds
+    #[inline]
+    pub fn get(&self, index: isize) -> Option<&u8> {
+        if self.contains_index(index) {
+            Some(unsafe { self.get_unchecked(index) })
+        } else {
+            None
+        }
+    }
+
+    /// Returns a mutable reference to the byte at `index`, or None if `index` is out of bounds
+    #[inline]
+    pub fn get_mut(&mut self, index: isize) -> Option<&mut u8> {
+        if self.contains_index(index) {
+            Some(unsafe { self.get_unchecked_mut(index) })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the bytes within `range`, or None if any part of `range` lies out of bounds
+    #[inline]
+    pub fn get_range<R: RangeBounds<isize>>(&self, range: R) -> Option<&[u8]> {
+        self.resolve_range(&range)
+            .map(|(start, end)| unsafe { &*std::ptr::slice_from_raw_parts(self.ptr.add(start), end - start) })
+    }
+
+    /// Returns the bytes within `range` mutably, or None if any part of `range` lies out of bounds
+    #[inline]
+    pub fn get_range_mut<R: RangeBounds<isize>>(&mut self, range: R) -> Option<&mut [u8]> {
+        let ptr = self.ptr;
+        self.resolve_range(&range)
+            .map(|(start, end)| unsafe { &mut *std::ptr::slice_from_raw_parts_mut(ptr.add(start), end - start) })
+    }
+
+    /// Returns a reference to the byte at `index` without checking the bounds
+    #[inline]
+    pub unsafe fn get_unchecked(&self, index: isize) -> &u8 {
+        &*self.ptr.offset(index)
+    }
+
+    /// Returns a mutable reference to the byte at `index` without checking the bounds
+    #[inline]
+    pub unsafe fn get_unchecked_mut(&mut self, index: isize) -> &mut u8 {
+        &mut *self.ptr.offset(index)
+    }
+
+    /// Returns the bytes within `range` without checking the bounds
+    #[inline]
+    pub unsafe fn get_range_unchecked(&self, range: Range<isize>) -> &[u8] {
+        &*std::ptr::slice_from_raw_parts(self.ptr.offset(range.start), (range.end - range.start) as usize)
+    }
+
+    /// Returns the bytes within `range` mutably without checking the bounds
+    #[inline]
+    pub unsafe fn get_range_unchecked_mut(&mut self, range: Range<isize>) -> &mut [u8] {
+        &mut *std::ptr::slice_from_raw_parts_mut(self.ptr.offset(range.start), (range.end - range.start) as usize)
+    }
+
+    #[inline]
+    fn contains_index(&self, index: isize) -> bool {
+        index >= 0 && (index as usize) < self.len
+    }
+
+    /// Translates `range` into a pair of (start, end) indices, so long as the range lies within the initialized bytes
+    fn resolve_range<R: RangeBounds<isize>>(&self, range: &R) -> Option<(usize, usize)> {
+        let start = match range.start_bound() {
+            Bound::Included(start) => *start,
+            Bound::Excluded(start) => start.checked_add(1)?,
+            Bound::Unbounded => 0
+        };
+
+        let end = match range.end_bound() {
+            Bound::Included(end) => end.checked_add(1)?,
+            Bound::Excluded(end) => *end,
+            Bound::Unbounded => self.len as isize
+        };
+
+        if start < 0 || start > end || end as usize > self.len {
+            None
+        } else {
+            Some((start as usize, end as usize))
+        }
     }
 
     /// Advance the cursor by 1
@@ -207,6 +590,306 @@ impl HyperVec {
         self.read_version.store(update, Ordering::SeqCst);
     }
 
+    /// Replaces the [SchedulingPolicy] deciding the order in which visitors are admitted. Intended to be chained onto a
+    /// constructor, before any visitor has been issued
+    pub fn with_scheduling_policy(mut self, policy: SchedulingPolicy) -> Self {
+        self.scheduler = Scheduler::new(policy);
+        self
+    }
+
+    /// Returns the [SchedulingPolicy] in use
+    pub fn scheduling_policy(&self) -> SchedulingPolicy {
+        self.scheduler.policy
+    }
+
+    /// Returns a snapshot of the buffer's ticket, contention, and allocation counters
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> HyperVecStats {
+        self.stats.snapshot()
+    }
+
+    /// Takes a place in line for a visitor of the given kind. `priority` only matters under [SchedulingPolicy::Priority]
+    pub(crate) fn enter(&self, kind: TicketKind, priority: u8) -> Place {
+        #[cfg(feature = "stats")]
+        self.stats.issued();
+
+        self.enter_counted(kind, priority).0
+    }
+
+    /// Takes a place in line for a visitor confined to the bytes [start, end). Range visitors issued back to back join one
+    /// epoch, wherein only those whose ranges overlap (and are not both readers) wait for one another
+    pub(crate) fn enter_range(&self, start: usize, end: usize, exclusive: bool) -> Position {
+        #[cfg(feature = "stats")]
+        self.stats.issued();
+
+        let entries = self.scheduler.entries.load(Ordering::SeqCst);
+        if let Some(position) = self.scheduler.state.lock().join_range(entries, start, end, exclusive) {
+            return position;
+        }
+
+        let (place, entries) = self.enter_counted(TicketKind::Write, 0);
+        self.scheduler.state.lock().open_range(place, entries, start, end, exclusive)
+    }
+
+    /// Like [HyperVec::enter], but also returns how many places have been taken in line, including this one
+    fn enter_counted(&self, kind: TicketKind, priority: u8) -> (Place, u64) {
+        let entries = self.scheduler.entries.fetch_add(1, Ordering::SeqCst) + 1;
+        if self.scheduler.policy == SchedulingPolicy::Fifo {
+            return (Place::Drawn(self.scheduler.issue(kind)), entries);
+        }
+
+        let (id, drawn) = {
+            let mut state = self.scheduler.state.lock();
+            let id = self.scheduler.enqueue(&mut state, kind, priority);
+            (id, self.scheduler.pump(&mut state, self.get_write_version(), self.get_read_version()))
+        };
+
+        self.wake_admitted(&drawn);
+        (Place::Queued(id), entries)
+    }
+
+    /// Whether `ticket` may proceed
+    #[inline]
+    pub(crate) fn is_admitted(&self, ticket: &Ticket) -> bool {
+        ticket.is_admitted(self.write_version.load(Ordering::SeqCst), self.read_version.load(Ordering::SeqCst))
+    }
+
+    /// Returns the ticket of the visitor at `place`, picking it up if it was drawn while the visitor was queued. The drawn
+    /// ticket stays on record until released, since every member of a range epoch picks up the same one
+    fn resolve(&self, place: &Cell<Place>) -> Option<Ticket> {
+        match place.get() {
+            Place::Drawn(ticket) => Some(ticket),
+            Place::Queued(id) => {
+                let ticket = *self.scheduler.state.lock().drawn.get(&id)?;
+                place.set(Place::Drawn(ticket));
+                Some(ticket)
+            }
+        }
+    }
+
+    /// Whether the visitor at `position` may proceed
+    #[inline]
+    pub(crate) fn admits(&self, position: &Position) -> bool {
+        let admitted = self.resolve(&position.place).map(|ticket| self.is_admitted(&ticket)).unwrap_or(false);
+        if !admitted {
+            return false;
+        }
+
+        position.admitted.set(true);
+        if !position.range.as_ref().map(|range| self.scheduler.state.lock().admit_range(range)).unwrap_or(true) {
+            return false;
+        }
+
+        #[cfg(feature = "stats")]
+        self.stats.admitted(position.kind(), &position.timing);
+        true
+    }
+
+    /// Checks whether the visitor at `position` may proceed, and if not, registers the task to be woken once it can
+    pub(crate) fn poll_admission(&self, position: &Position, cx: &mut Context) -> bool {
+        loop {
+            if self.admits(position) {
+                return true;
+            }
+
+            let key = position.place.get();
+            self.scheduler.wakers.register(key, cx.waker());
+            // The visitor ahead may have been released, or our ticket drawn, between the check above and registering
+            if self.admits(position) {
+                return true;
+            }
+
+            // If our ticket was drawn in the meantime, register again under it
+            if position.place.get() == key {
+                return false;
+            }
+        }
+    }
+
+    /// Finishes with the visitor at `position`. A range visitor leaves its epoch, waking the members waiting on it, and the
+    /// epoch's ticket is released along with its last member
+    pub(crate) fn release_position(&self, position: &Position) {
+        #[cfg(feature = "stats")]
+        self.stats.completed(&position.timing);
+
+        let range = match &position.range {
+            Some(range) => range,
+            None => return self.release(position.place.get(), position.admitted.get())
+        };
+
+        let (place, emptied, admitted, drawn) = {
+            let mut state = self.scheduler.state.lock();
+            let (place, emptied, admitted) = state.leave_range(range);
+            let drawn = match place {
+                Place::Queued(id) => state.drawn.get(&id).cloned(),
+                Place::Drawn(ticket) => Some(ticket)
+            };
+
+            (place, emptied, admitted, drawn)
+        };
+
+        self.scheduler.wakers.wake_where(|waiting| waiting == place || drawn.map(|ticket| waiting == Place::Drawn(ticket)).unwrap_or(false));
+        if emptied {
+            self.release(place, admitted);
+        }
+    }
+
+    /// Finishes with the visitor at `place`. A ticket its visitor saw `admitted` is released, while one still waiting is
+    /// cancelled and later skipped in order. Every queued visitor this unblocks is then woken
+    fn release(&self, place: Place, admitted: bool) {
+        if let (SchedulingPolicy::Fifo, Place::Drawn(ticket), true) = (self.scheduler.policy, place, admitted) {
+            return self.release_admitted(ticket);
+        }
+
+        let drawn = {
+            let mut state = self.scheduler.state.lock();
+            // Counted before the ticket is cancelled, so that a release racing ahead of us without the lock sees that it must
+            // settle it
+            let _ = self.scheduler.unsettled.fetch_add(1, Ordering::SeqCst);
+            let ticket = match place {
+                Place::Drawn(ticket) => {
+                    // The ticket may have been picked up from the record of drawn tickets, which FIFO never fills
+                    if !state.drawn.is_empty() {
+                        state.drawn.retain(|_, drawn| *drawn != ticket);
+                    }
+                    Some(ticket)
+                }
+
+                Place::Queued(id) => state.drawn.remove(&id)
+            };
+
+            let cancelled = match ticket {
+                Some(ticket) if admitted => {
+                    self.advance_version(ticket.kind);
+                    false
+                }
+
+                Some(ticket) => {
+                    state.cancelled.record(ticket);
+                    self.scheduler.wakers.remove(Place::Drawn(ticket));
+                    true
+                }
+
+                None => {
+                    if let Place::Queued(id) = place {
+                        state.dequeue(id);
+                    }
+                    false
+                }
+            };
+
+            self.scheduler.wakers.remove(place);
+            if !cancelled {
+                let _ = self.scheduler.unsettled.fetch_sub(1, Ordering::SeqCst);
+            }
+
+            self.scheduler.settle(&mut state, &self.write_version, &self.read_version);
+            self.scheduler.pump(&mut state, self.get_write_version(), self.get_read_version())
+        };
+
+        self.wake_admitted(&drawn);
+    }
+
+    /// Releases an admitted FIFO ticket. Only the atomics are touched, unless a cancelled ticket may be waiting to be skipped
+    fn release_admitted(&self, ticket: Ticket) {
+        self.advance_version(ticket.kind);
+        self.scheduler.wakers.remove(Place::Drawn(ticket));
+        if self.scheduler.unsettled.load(Ordering::SeqCst) != 0 {
+            let mut state = self.scheduler.state.lock();
+            self.scheduler.settle(&mut state, &self.write_version, &self.read_version);
+        }
+
+        self.wake_admitted(&[]);
+    }
+
+    /// Counts one more released ticket of the given kind
+    #[inline]
+    fn advance_version(&self, kind: TicketKind) {
+        let _ = match kind {
+            TicketKind::Read => self.read_version.fetch_add(1, Ordering::SeqCst),
+            TicketKind::Write => self.write_version.fetch_add(1, Ordering::SeqCst)
+        };
+    }
+
+    /// Wakes every waiting visitor which may now proceed, along with the queued visitors in `drawn`, which must pick up their tickets
+    fn wake_admitted(&self, drawn: &[u64]) {
+        self.scheduler.wakers.wake_where(|waiting| match waiting {
+            Place::Drawn(ticket) => self.is_admitted(&ticket),
+            Place::Queued(id) => drawn.contains(&id)
+        });
+    }
+
+    /// Copies the `T` stored at `offset` out, regardless of how the offset is aligned. Returns [MemError::OUT_OF_BOUNDS] if
+    /// the `T` would extend past the initialized bytes
+    pub fn read_unaligned<T: HyperPod>(&self, offset: usize) -> InformationResult<'static, T> {
+        self.check_unaligned::<T>(offset)?;
+        Ok(unsafe { std::ptr::read_unaligned(self.ptr.add(offset) as *const T) })
+    }
+
+    /// Copies `value` into the bytes at `offset`, regardless of how the offset is aligned. Returns [MemError::OUT_OF_BOUNDS]
+    /// if the `T` would extend past the initialized bytes
+    pub fn write_unaligned<T: HyperPod>(&mut self, offset: usize, value: T) -> InformationResult<'static, ()> {
+        self.check_unaligned::<T>(offset)?;
+        unsafe { std::ptr::write_unaligned(self.ptr.add(offset) as *mut T, value) };
+        Ok(())
+    }
+
+    /// Checks that a `T` at `offset` lies within the initialized bytes
+    fn check_unaligned<T>(&self, offset: usize) -> InformationResult<'static, ()> {
+        let size = std::mem::size_of::<T>();
+        match offset.checked_add(size) {
+            Some(end) if end <= self.len => Ok(()),
+            _ => MemError::throw_out_of_bounds(offset as isize, size, self.len)
+        }
+    }
+
+    /// Copies the leading bytes out as a `T` without drawing a ticket. If a WriteVisitor writes during the copy, the copy
+    /// is discarded and retried, so the returned value is never torn, and a reallocation waits for the copy to finish before
+    /// freeing the bytes. Intended for small, hot metadata that many tasks poll.
+    /// Panics if fewer than size_of::<T>() bytes are initialized
+    pub fn read_consistent<T: HyperPod>(&self) -> T {
+        let size = std::mem::size_of::<T>();
+        loop {
+            let before = self.sequence.load(Ordering::Acquire);
+            if before & SEQUENCE_WRITERS != 0 {
+                std::sync::atomic::spin_loop_hint();
+                continue;
+            }
+
+            // Pinning the allocation holds off any reallocation which has yet to begin; one which began after `before` was
+            // loaded is caught by loading the sequence again, whereupon the pin is dropped so that it may proceed
+            let _ = self.pinned.fetch_add(1, Ordering::SeqCst);
+            std::sync::atomic::fence(Ordering::SeqCst);
+            if self.sequence.load(Ordering::SeqCst) != before {
+                let _ = self.pinned.fetch_sub(1, Ordering::Release);
+                continue;
+            }
+
+            // Reallocating moves the pointer and length, so both are read within the window and trusted only once it validates
+            let (ptr, len) = unsafe { (std::ptr::read_volatile(&self.ptr), std::ptr::read_volatile(&self.len)) };
+            let value = if size <= len { Some(unsafe { std::ptr::read_unaligned(ptr as *const T) }) } else { None };
+            let _ = self.pinned.fetch_sub(1, Ordering::Release);
+            std::sync::atomic::fence(Ordering::Acquire);
+            if self.sequence.load(Ordering::Relaxed) == before {
+                return value.unwrap_or_else(|| panic!("cannot read {} bytes from a buffer of length {}", size, len));
+            }
+        }
+    }
+
+    /// Counts a visitor as writing ahead of its first write
+    #[inline]
+    pub(crate) fn begin_sequence(&self) {
+        let writers = self.sequence.fetch_add(1, Ordering::Relaxed) & SEQUENCE_WRITERS;
+        debug_assert!(writers < SEQUENCE_WRITERS, "the writer count overflowed into the sequence's generation");
+        std::sync::atomic::fence(Ordering::Release);
+    }
+
+    /// Moves a visitor which has finished writing from the low bits of the sequence to the high bits
+    #[inline]
+    pub(crate) fn end_sequence(&self) {
+        let _ = self.sequence.fetch_add(SEQUENCE_WRITERS, Ordering::Release);
+    }
+
     /// Returns the buffer's endianness
     pub fn get_endianness(&self) -> &Endianness {
         &self.endianness
@@ -225,30 +908,159 @@ impl HyperVec {
         self.corrupt
     }
 
-    /// Extends the layout and increases the length
-    #[allow(unused)]
+    /// Appends `byte` onto the end of the buffer. Unlike [BytePusher], this ignores the cursor
+    #[inline]
+    pub fn push(&mut self, byte: u8) {
+        self.reserve(1);
+        unsafe { *self.ptr.add(self.len) = byte };
+        self.len += 1;
+    }
+
+    /// Appends `bytes` onto the end of the buffer. Unlike [BytePusher], this ignores the cursor
+    pub fn extend_from_slice(&mut self, bytes: &[u8]) {
+        self.reserve(bytes.len());
+        unsafe { std::ptr::copy_nonoverlapping(bytes.as_ptr(), self.ptr.add(self.len), bytes.len()) };
+        self.len += bytes.len();
+    }
+
+    /// Shortens the buffer to `len` bytes, keeping the capacity. This has no effect if `len` is not less than the current length
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.len {
+            let removed = self.len - len;
+            self.adjust_for_edit(len, removed, 0);
+            self.len = len;
+        }
+    }
+
+    /// Removes every byte, keeping the capacity. The cursor returns to the start
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Resizes the buffer to `new_len` bytes, filling any new bytes with `value`
+    pub fn resize(&mut self, new_len: usize, value: u8) {
+        if new_len > self.len {
+            self.reserve(new_len - self.len);
+            unsafe { std::ptr::write_bytes(self.ptr.add(self.len), value, new_len - self.len) };
+            self.len = new_len;
+        } else {
+            self.truncate(new_len);
+        }
+    }
+
+    /// Inserts `bytes` at index `at`, shifting the bytes that follow towards the end
+    pub fn insert_slice(&mut self, at: usize, bytes: &[u8]) {
+        assert!(at <= self.len, "insertion index {} exceeds the length {}", at, self.len);
+        self.replace_range(at, at, bytes);
+    }
+
+    /// Removes the bytes within `range`, shifting the bytes that follow towards the start
+    pub fn remove_range<R: RangeBounds<usize>>(&mut self, range: R) {
+        let (start, end) = crate::util::resolve_range(&range, self.len);
+        self.replace_range(start, end, &[]);
+    }
+
+    /// Removes the bytes within `range`, returning them through an iterator
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> IntoIter<u8> {
+        self.splice(range, &[])
+    }
+
+    /// Replaces the bytes within `range` with `replace_with` (which may differ in length), returning the removed bytes through an iterator
+    pub fn splice<R: RangeBounds<usize>>(&mut self, range: R, replace_with: &[u8]) -> IntoIter<u8> {
+        let (start, end) = crate::util::resolve_range(&range, self.len);
+        let removed = self.as_slice()[start..end].to_vec();
+        self.replace_range(start, end, replace_with);
+        removed.into_iter()
+    }
+
+    /// Copies the bytes within `src` to the position `dest`, overwriting the bytes therein. The regions may overlap
+    pub fn copy_within<R: RangeBounds<usize>>(&mut self, src: R, dest: usize) {
+        self.as_mut_slice().copy_within(src, dest);
+    }
+
+    /// Appends a copy of the bytes within `src` onto the end of the buffer
+    pub fn extend_from_within<R: RangeBounds<usize>>(&mut self, src: R) {
+        let (start, end) = crate::util::resolve_range(&src, self.len);
+        let amt = end - start;
+        self.reserve(amt);
+        unsafe { std::ptr::copy_nonoverlapping(self.ptr.add(start), self.ptr.add(self.len), amt) };
+        self.len += amt;
+    }
+
+    /// Replaces the bytes [start, end) with `replace_with`, moving the bytes that follow into place
+    fn replace_range(&mut self, start: usize, end: usize, replace_with: &[u8]) {
+        debug_assert!(start <= end && end <= self.len);
+        let removed = end - start;
+        let inserted = replace_with.len();
+        if inserted > removed {
+            self.reserve(inserted - removed);
+        }
+
+        unsafe {
+            std::ptr::copy(self.ptr.add(end), self.ptr.add(start + inserted), self.len - end);
+            std::ptr::copy_nonoverlapping(replace_with.as_ptr(), self.ptr.add(start), inserted);
+        }
+
+        self.len = self.len - removed + inserted;
+        self.adjust_for_edit(start, removed, inserted);
+    }
+
+    /// Keeps the cursor and the partition map consistent after `removed` bytes at `at` were replaced by `inserted` bytes. A cursor
+    /// past the edited region follows its byte, whereas a cursor within the edited region moves to its start
+    fn adjust_for_edit(&mut self, at: usize, removed: usize, inserted: usize) {
+        let (at, removed, inserted) = (at as isize, removed as isize, inserted as isize);
+        if self.cursor >= at + removed {
+            self.cursor += inserted - removed;
+        } else if self.cursor > at {
+            self.cursor = at;
+        }
+
+        if let Some(map) = self.partition_map.as_mut() {
+            map.adjust_for_edit(at, removed, inserted);
+        }
+    }
+
+    /// Increases the length by `additional_bytes`, growing the capacity if necessary. The new bytes are zeroed
     #[inline]
     pub fn extend(&mut self, additional_bytes: usize) {
-        if let Ok((layout, pos_new)) = self.layout.extend(Layout::array::<u8>(additional_bytes).unwrap()) {
-            println!("[REALLOC] additional bytes: {}", additional_bytes);
-            println!("[REALLOC] new layout size, pos: {}, {}, --- {}", layout.size(), self.layout.size(), pos_new);
-            assert_eq!(self.layout.size(), pos_new);
-            println!("[REALLOC] self.len (before) {}", self.len);
-            assert_eq!(layout.size(), additional_bytes + self.len);
-            self.len += additional_bytes;
-            println!("[REALLOC] self.len (after) {}", self.len);
-            self.ptr = unsafe { std::alloc::realloc(self.ptr, layout, self.len) };
-            self.layout = layout;
-            println!("[REALLOC] {}", self);
+        self.reserve(additional_bytes);
+        unsafe { std::ptr::write_bytes(self.ptr.add(self.len), 0, additional_bytes) };
+        self.len += additional_bytes;
+    }
+}
+
+/// Allocates `layout`, returning a dangling (yet well-aligned) pointer for zero-sized layouts
+#[inline]
+pub(crate) fn allocate(layout: Layout, zeroed: bool) -> *mut u8 {
+    if layout.size() == 0 {
+        return layout.align() as *mut u8;
+    }
+
+    let ptr = unsafe {
+        if zeroed {
+            std::alloc::alloc_zeroed(layout)
+        } else {
+            std::alloc::alloc(layout)
         }
+    };
+
+    if ptr.is_null() {
+        std::alloc::handle_alloc_error(layout)
     }
+
+    ptr
 }
 
+/// The low bits of [HyperVec::sequence] which count the WriteVisitors currently writing. Adding it once a visitor finishes
+/// both discounts the visitor and bumps the high bits
+const SEQUENCE_WRITERS: u64 = 0xFFFF_FFFF;
+
 /// Allows asynchronous data execution once it's spot in line reaches the 'front'.
 pub struct WriteVisitor<'visit, T: ?Sized> {
-    ptr: *mut HyperVec,
-    ticket_number: usize,
-    bytes_written: usize,
+    pub(crate) ptr: *mut HyperVec,
+    pub(crate) position: Position,
+    /// Whether this visitor is counted as writing in the sequence
+    writing: Cell<bool>,
     _phantom: PhantomData<&'visit T>,
 }
 
@@ -260,20 +1072,60 @@ impl<'visit, T: ?Sized> !Sync for WriteVisitor<'visit, T> {}
 impl<'visit, T: ?Sized> Drop for WriteVisitor<'visit, T> {
     fn drop(&mut self) {
         unsafe {
-            //println!("DROPPING tx Ticket {}", self.ticket_number);
-            let hvec = &mut *self.ptr;
-            if self.bytes_written != 0 {
-                hvec.extend(self.bytes_written);
+            // Only borrowed immutably, since the visitor may have been issued by a handle shared with other threads
+            let hvec = &*self.ptr;
+            if self.writing.get() {
+                hvec.end_sequence();
             }
-            hvec.get_and_increment_write_version();
+            hvec.release_position(&self.position);
         }
     }
 }
 
-impl<'visit, T: ?Sized> WriteVisitor<'visit, T> {
-    /// Creates a new WriteVisitor
-    pub fn new(hvec_ptr: *mut HyperVec, ticket_number: usize) -> Self {
-        Self { ptr: hvec_ptr, ticket_number, _phantom: PhantomData, bytes_written: 0 }
+impl<'visit, T> WriteVisitor<'visit, T> {
+    /// Creates a new visitor which waits at `position`
+    pub(crate) fn new(hvec_ptr: *mut HyperVec, position: Position) -> Self {
+        Self { ptr: hvec_ptr, position, _phantom: PhantomData, writing: Cell::new(false) }
+    }
+
+    /// Marks the start of writing for optimistic readers. Must only be called once admitted
+    #[inline]
+    pub(crate) fn begin_write(&self) {
+        if !self.writing.replace(true) {
+            unsafe { (*self.ptr).begin_sequence() };
+        }
+    }
+
+    /// Extends the buffer by `pre_alloc` bytes, aligned for a `T`, then checks that the bytes can hold a `T`. Only done once
+    /// admitted, since reallocating may move the bytes out from under another visitor; for the same reason, range-scoped
+    /// visitors, which share the buffer with their epoch, may not extend it
+    fn pre_allocate(&self, pre_alloc: Option<usize>) -> InformationResult<'visit, ()> {
+        if let Some(alloc) = pre_alloc {
+            if self.position.range.is_some() {
+                return MemError::throw("a range-scoped visitor cannot extend the buffer".as_bytes());
+            }
+
+            self.begin_write();
+            unsafe { (*self.ptr).extend_aligned(alloc, std::mem::align_of::<T>()) };
+        }
+
+        self.check_fit()
+    }
+
+    /// Checks that the bytes this visitor covers are enough to hold a `T`, and aligned for it. Must only be called once
+    /// admitted, since the visitors ahead may grow, and thereby move, the buffer
+    pub(crate) fn check_fit(&self) -> InformationResult<'visit, ()> {
+        unsafe {
+            let hvec = &*self.ptr;
+            let (offset, len) = self.position.region(hvec.len);
+            hvec.check_fit::<T>(offset, len)
+        }
+    }
+
+    /// Returns a pointer to the object this visitor covers. Must only be dereferenced once admitted
+    #[inline]
+    pub(crate) unsafe fn object_ptr(&self) -> *mut T {
+        (*self.ptr).ptr.add(self.position.region((*self.ptr).len).0) as *mut T
     }
 
     /// Consumes the visitor. Make sure to enter at least the number of bytes you expect to extend into the buf in `pre_alloc` (if the current len does not suffice).
@@ -288,15 +1140,35 @@ impl<'visit, T: ?Sized> WriteVisitor<'visit, T> {
     /// [2]
     #[inline]
     pub async fn visit<Fx>(self, pre_alloc: Option<usize>, subroutine: Fx) -> Result<(), MemError<'visit, &'visit [u8]>> where Fx: Fn(&Self) -> Option<usize> {
-        if let Some(alloc) = pre_alloc {
-            unsafe { (*(self).ptr).extend(alloc) };
-        }
-
         (&self).await.and_then(move |_| {
+            self.pre_allocate(pre_alloc)?;
             self.visit_inner(pre_alloc, &subroutine)
         })
     }
 
+    /// Like [WriteVisitor::visit], but gives up once `timeout` elapses without the ticket being admitted, returning
+    /// [MemError::TIMED_OUT]. The abandoned ticket is skipped in order, so the visitors behind it are unaffected
+    pub async fn visit_timeout<Fx>(self, timeout: Duration, pre_alloc: Option<usize>, subroutine: Fx) -> InformationResult<'visit, ()> where Fx: Fn(&Self) -> Option<usize> {
+        unsafe { AdmissionTimeout::new(&*self.ptr, &self.position, timeout) }.await?;
+        self.pre_allocate(pre_alloc)?;
+        self.visit_inner(pre_alloc, &subroutine)
+    }
+
+    /// Parks the calling thread until the ticket is admitted, then runs `subroutine` as [WriteVisitor::visit] would. For
+    /// synchronous callers; the thread is unparked as soon as the visitor ahead releases its ticket
+    pub fn visit_blocking<Fx>(self, pre_alloc: Option<usize>, subroutine: Fx) -> InformationResult<'visit, ()> where Fx: Fn(&Self) -> Option<usize> {
+        unsafe { wait_for_admission(&*self.ptr, &self.position, None) }?;
+        self.pre_allocate(pre_alloc)?;
+        self.visit_inner(pre_alloc, &subroutine)
+    }
+
+    /// Like [WriteVisitor::visit_blocking], but gives up once `timeout` elapses, returning [MemError::TIMED_OUT]
+    pub fn visit_blocking_timeout<Fx>(self, timeout: Duration, pre_alloc: Option<usize>, subroutine: Fx) -> InformationResult<'visit, ()> where Fx: Fn(&Self) -> Option<usize> {
+        unsafe { wait_for_admission(&*self.ptr, &self.position, Some(timeout)) }?;
+        self.pre_allocate(pre_alloc)?;
+        self.visit_inner(pre_alloc, &subroutine)
+    }
+
     /// Quickly checks to see if the current writer is allowed to write, and if not, immediately returns with MemError::NOT_READY
     #[inline]
     pub unsafe fn try_visit<Fx>(self, pre_alloc: Option<usize>, subroutine: Fx) -> InformationResult<'visit, ()>
@@ -311,15 +1183,16 @@ impl<'visit, T: ?Sized> WriteVisitor<'visit, T> {
     #[inline]
     fn visit_inner<Fx>(self, pre_alloc: Option<usize>, subroutine: &Fx) -> InformationResult<'visit, ()> where Fx: Fn(&Self) -> Option<usize> {
         unsafe {
-            //println!("Will exec subroutine {}", self.ticket_number);
-            let initial_size = (*(self).ptr).len;
+            let initial_size = self.position.region((*(self).ptr).len).1;
             let pre_alloc_amt = pre_alloc.unwrap_or(0);
 
             match subroutine(&self) {
                 Some(bytes_added) => {
                     if bytes_added > initial_size + pre_alloc_amt {
                         (*self.ptr).corrupt = true;
-                        let bytes = (*self.ptr).bytes();
+                        #[cfg(feature = "stats")]
+                        (*self.ptr).stats.corrupted();
+                        let bytes = (*self.ptr).get_full_bytes();
                         MemError::throw_corrupt(bytes)
                     } else {
                         Ok(())
@@ -336,15 +1209,17 @@ impl<'visit, T: ?Sized> WriteVisitor<'visit, T> {
     #[inline]
     fn is_ready(&self) -> bool {
         unsafe {
-            self.ticket_number == (*self.ptr).get_write_version()
+            (*self.ptr).admits(&self.position)
         }
     }
 
-    /// Returns a mutable reference to the underlying object if available
+    /// Returns a mutable reference to the underlying object if available. None is also returned once admitted if the bytes
+    /// can no longer hold a `T`
     #[inline]
     pub fn write(&self) -> Option<&mut T> {
-        if self.is_ready() {
-            unsafe { Some((*self.ptr).cast_unchecked_mut()) }
+        if self.is_ready() && self.check_fit().is_ok() {
+            self.begin_write();
+            unsafe { Some(&mut *self.object_ptr()) }
         } else {
             None
         }
@@ -353,8 +1228,13 @@ impl<'visit, T: ?Sized> WriteVisitor<'visit, T> {
     /// Returns a mutable reference to the underlying object if available
     #[inline]
     pub fn write_array(&self) -> Option<&mut [T]> where for<'a> T: Sized + 'a {
-        if self.is_ready() {
-            unsafe { Some((*self.ptr).cast_unchecked_mut_array()) }
+        if self.is_ready() && self.check_fit().is_ok() {
+            self.begin_write();
+            unsafe {
+                let (offset, len) = self.position.region((*self.ptr).len);
+                let base_ptr = (*self.ptr).ptr.add(offset) as *mut T;
+                Some(&mut *std::ptr::slice_from_raw_parts_mut(base_ptr, len / std::mem::size_of::<T>()))
+            }
         } else {
             None
         }
@@ -365,8 +1245,8 @@ impl<'visit, T: ?Sized> Future for & WriteVisitor<'visit, T> {
     type Output = InformationResult<'visit, ()>;
 
     #[inline]
-    fn poll(self: Pin<&mut Self>, _: &mut Context) -> Poll<Self::Output> {
-        if self.is_ready() {
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        if unsafe { (*self.ptr).poll_admission(&self.position, cx) } {
             Poll::Ready(Ok(()))
         } else {
             Poll::Pending
@@ -377,9 +1257,8 @@ impl<'visit, T: ?Sized> Future for & WriteVisitor<'visit, T> {
 
 /// Allows asynchronous data execution once it's spot in line reaches the 'front'.
 pub struct ReadVisitor<'visit, T: ?Sized> {
-    ptr: *mut HyperVec,
-    ticket_number: usize,
-    bytes_written: usize,
+    pub(crate) ptr: *mut HyperVec,
+    pub(crate) position: Position,
     _phantom: PhantomData<&'visit T>,
 }
 
@@ -391,20 +1270,32 @@ impl<'visit, T: ?Sized> !Sync for ReadVisitor<'visit, T> {}
 impl<'visit, T: ?Sized> Drop for ReadVisitor<'visit, T> {
     fn drop(&mut self) {
         unsafe {
-            //println!("DROPPING rx Ticket {}", self.ticket_number);
-            let hvec = &mut *self.ptr;
-            if self.bytes_written != 0 {
-                hvec.extend(self.bytes_written);
-            }
-            hvec.get_and_increment_read_version();
+            (*self.ptr).release_position(&self.position);
         }
     }
 }
 
-impl<'visit, T: ?Sized> ReadVisitor<'visit, T> {
-    /// Creates a new WriteVisitor
-    pub fn new(hvec_ptr: *mut HyperVec, ticket_number: usize) -> Self {
-        Self { ptr: hvec_ptr, ticket_number, _phantom: PhantomData, bytes_written: 0 }
+impl<'visit, T> ReadVisitor<'visit, T> {
+    /// Creates a new visitor which waits at `position`
+    pub(crate) fn new(hvec_ptr: *mut HyperVec, position: Position) -> Self {
+        Self { ptr: hvec_ptr, position, _phantom: PhantomData }
+    }
+
+    /// Checks that the bytes this visitor covers are still enough to hold a `T`, and aligned for it. Must only be called once
+    /// admitted, since the writers ahead may move the buffer
+    pub(crate) fn check_fit(&self) -> InformationResult<'visit, ()> {
+        unsafe {
+            let hvec = &*self.ptr;
+            let (offset, len) = self.position.region(hvec.len);
+            hvec.check_fit::<T>(offset, len)
+        }
+    }
+
+    /// Returns the object this visitor covers. Must only be called once admitted
+    #[inline]
+    pub(crate) unsafe fn get_unchecked(&self) -> &T {
+        let ptr = (*self.ptr).ptr.add(self.position.region((*self.ptr).len).0);
+        std::mem::transmute_copy::<*mut u8, &T>(&ptr)
     }
 
     /// Consumes the visitor. Make sure to enter at least the number of bytes you expect to write in `pre_alloc` (if the current len does not suffice).
@@ -424,15 +1315,19 @@ impl<'visit, T: ?Sized> ReadVisitor<'visit, T> {
         // We need to check the write version to make sure it hasn't changed while waiting. IF a read
         // occurs simultaneous to a write, then that could mean that the bytes switched midway through reading
         // in that case, we must read again for consistency
-        self.await.and_then(move |_|  {
-            let start_vers = unsafe { (*self.ptr).get_write_version() };
-            subroutine(Some(&self));
-            if start_vers ==  unsafe { (*self.ptr).get_write_version() } {
-                Ok(())
-            } else {
-                Err(MemError::OUT_OF_SYNC)
-            }
-        })
+        self.await.and_then(|_| self.check_fit()).and_then(move |_| self.read_inner(&subroutine))
+    }
+
+    #[inline]
+    fn read_inner<Fx>(&self, subroutine: &Fx) -> InformationResult<'visit, ()>
+        where Fx: Fn(Option<&Self>) {
+        let start_vers = unsafe { (*self.ptr).get_write_version() };
+        subroutine(Some(&self));
+        if start_vers ==  unsafe { (*self.ptr).get_write_version() } {
+            Ok(())
+        } else {
+            Err(MemError::OUT_OF_SYNC)
+        }
     }
 
     /// This function recursively calls try_visit so long as an Error is called. An Error occurs when:
@@ -449,7 +1344,11 @@ impl<'visit, T: ?Sized> ReadVisitor<'visit, T> {
             Ok(_) => {Ok(())},
             Err(e) => {
                 match e {
-                    MemError::OUT_OF_SYNC => {Err(e)},
+                    MemError::OUT_OF_SYNC => {
+                        #[cfg(feature = "stats")]
+                        (*(*self_ptr).ptr).stats.out_of_sync();
+                        Err(e)
+                    },
                     // Exit if there is any other type of error
                     _ => {Ok(())}
                 }
@@ -478,18 +1377,44 @@ impl<'visit, T: ?Sized> ReadVisitor<'visit, T> {
     }
 
 
+    /// Like [ReadVisitor::visit], but gives up once `timeout` elapses without the ticket being admitted, returning
+    /// [MemError::TIMED_OUT]. The abandoned ticket is skipped in order, so the visitors behind it are unaffected
+    pub async fn visit_timeout<Fx>(self, timeout: Duration, subroutine: Fx) -> InformationResult<'visit, ()>
+        where Fx: Fn(Option<&Self>) {
+        unsafe { AdmissionTimeout::new(&*self.ptr, &self.position, timeout) }.await?;
+        self.try_visit(subroutine).await
+    }
+
+    /// Parks the calling thread until the ticket is admitted, then runs `subroutine` as [ReadVisitor::visit] would. For
+    /// synchronous callers; the thread is unparked as soon as the writer ahead releases its ticket
+    pub fn visit_blocking<Fx>(self, subroutine: Fx) -> InformationResult<'visit, ()>
+        where Fx: Fn(Option<&Self>) {
+        unsafe { wait_for_admission(&*self.ptr, &self.position, None) }?;
+        self.check_fit()?;
+        self.read_inner(&subroutine)
+    }
+
+    /// Like [ReadVisitor::visit_blocking], but gives up once `timeout` elapses, returning [MemError::TIMED_OUT]
+    pub fn visit_blocking_timeout<Fx>(self, timeout: Duration, subroutine: Fx) -> InformationResult<'visit, ()>
+        where Fx: Fn(Option<&Self>) {
+        unsafe { wait_for_admission(&*self.ptr, &self.position, Some(timeout)) }?;
+        self.check_fit()?;
+        self.read_inner(&subroutine)
+    }
+
     #[inline]
     fn is_ready(&self) -> bool {
         unsafe {
-            self.ticket_number == (*self.ptr).get_read_version()
+            (*self.ptr).admits(&self.position)
         }
     }
 
-    /// Returns a mutable reference to the underlying object if available
+    /// Returns a reference to the underlying object if available. None is also returned once admitted if the bytes can no
+    /// longer hold a `T`
     #[inline]
     pub fn read(&self) -> Option<&T> {
-        if self.is_ready() {
-            unsafe { Some((*self.ptr).cast_unchecked()) }
+        if self.is_ready() && self.check_fit().is_ok() {
+            unsafe { Some(self.get_unchecked()) }
         } else {
             None
         }
@@ -498,8 +1423,12 @@ impl<'visit, T: ?Sized> ReadVisitor<'visit, T> {
     /// Returns a mutable reference to the underlying object if available
     #[inline]
     pub fn read_array(&self) -> Option<&[T]> where for<'a> T: Sized + 'a {
-        if self.is_ready() {
-            unsafe { Some((*self.ptr).cast_unchecked_array()) }
+        if self.is_ready() && self.check_fit().is_ok() {
+            unsafe {
+                let (offset, len) = self.position.region((*self.ptr).len);
+                let base_ptr = (*self.ptr).ptr.add(offset) as *const T;
+                Some(&*std::ptr::slice_from_raw_parts(base_ptr, len / std::mem::size_of::<T>()))
+            }
         } else {
             None
         }
@@ -510,8 +1439,8 @@ impl<'visit, T: ?Sized> Future for &ReadVisitor<'visit, T> {
     type Output = InformationResult<'visit, ()>;
 
     #[inline]
-    fn poll(self: Pin<&mut Self>, _: &mut Context) -> Poll<Self::Output> {
-        if self.is_ready() {
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        if unsafe { (*self.ptr).poll_admission(&self.position, cx) } {
             Poll::Ready(Ok(()))
         } else {
             Poll::Pending
@@ -520,6 +1449,7 @@ impl<'visit, T: ?Sized> Future for &ReadVisitor<'visit, T> {
 }
 
 /// For determining endianness of the HyperVec
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[repr(C)]
 pub enum Endianness {
     /// Little Endian
@@ -542,7 +1472,7 @@ impl Endianness {
     }
 
     /// Returns true if self is big endian
-    pub fn is_be(&self) -> bool {
+    pub fn is_be(self) -> bool {
         match self {
             Endianness::BE => {true},
             _ => false
@@ -551,7 +1481,7 @@ impl Endianness {
 
     /// Returns true if self is little endian
     #[allow(dead_code)]
-    pub fn is_le(&self) -> bool {
+    pub fn is_le(self) -> bool {
         !self.is_be()
     }
 
@@ -575,7 +1505,7 @@ impl Display for HyperVec {
             }
         };
 
-        write!(f, "[HyperVec] [length={}] [cursor={}] [read_version={}] [write_version={}] [Endianness={}]",
-        self.len, self.cursor, self.get_read_version(), self.get_write_version(), endianness)
+        write!(f, "[HyperVec] [length={}] [capacity={}] [cursor={}] [read_version={}] [write_version={}] [Endianness={}]",
+        self.len, self.capacity(), self.cursor, self.get_read_version(), self.get_write_version(), endianness)
     }
 }
\ No newline at end of file
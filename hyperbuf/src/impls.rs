@@ -1,12 +1,20 @@
 /*
  * Copyright (c) 2019. The information/code/data contained within this file and all other files with the same copyright are protected under US Statutes. You must have explicit written access by Thomas P. Braun in order to access, view, modify, alter, or apply this code in any context commercial or non-commercial. If you have this code but were not given explicit written access by Thomas P. Braun, you must destroy the information herein for legal safety. You agree that if you apply the concepts herein without any written access, Thomas P. Braun will seek the maximum possible legal retribution.
  */
-use std::ops::{Index, IndexMut, Range};
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt::{Debug, Formatter};
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::mem::ManuallyDrop;
+use std::ops::{Deref, DerefMut, Index, IndexMut, Range, RangeBounds, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
 use std::ptr::NonNull;
-use bytes::BufMut;
+use bytes::{Buf, BufMut};
 
-use crate::results::MemError;
+use crate::results::{InformationResult, MemError};
 use crate::hypervec::{HyperVec, ReadVisitor, WriteVisitor, Endianness};
+use crate::scheduler::{Position, TicketKind};
+use crate::pod::HyperPod;
 use std::sync::atomic::AtomicUsize;
 use std::alloc::{Alloc, Layout};
 
@@ -31,8 +39,8 @@ impl HyperVecSerde {
 }
 
 impl Into<HyperVecSerde> for HyperVec  {
-    fn into(mut self) -> HyperVecSerde {
-        let bytes =  unsafe { self.get_full_bytes_mut().to_vec()};
+    fn into(self) -> HyperVecSerde {
+        let bytes = self.as_slice().to_vec();
         let cursor = self.cursor;
         let read_version = self.get_read_version();
         let write_version = self.get_write_version();
@@ -49,7 +57,7 @@ impl Into<HyperVecSerde> for HyperVec  {
 
 impl Into<HyperVec> for HyperVecSerde {
     fn into(self) -> HyperVec{
-        let mut hvec = HyperVec::wrap_bytes(self.0);
+        let mut hvec = HyperVec::from(self.0);
         hvec.cursor = self.1;
         hvec.read_version = AtomicUsize::new(self.2);
         hvec.write_version = AtomicUsize::new(self.3);
@@ -58,6 +66,146 @@ impl Into<HyperVec> for HyperVecSerde {
     }
 }
 
+impl From<Vec<u8>> for HyperVec {
+    /// Adopts the allocation of `vec` without copying
+    fn from(vec: Vec<u8>) -> Self {
+        let mut vec = ManuallyDrop::new(vec);
+        let layout = Layout::array::<u8>(vec.capacity()).unwrap();
+        let ptr = if vec.capacity() == 0 {
+            crate::hypervec::allocate(layout, false)
+        } else {
+            vec.as_mut_ptr()
+        };
+
+        HyperVec::from_raw_parts(ptr, vec.len(), layout)
+    }
+}
+
+impl From<Box<[u8]>> for HyperVec {
+    /// Adopts the allocation of `bytes` without copying
+    fn from(bytes: Box<[u8]>) -> Self {
+        HyperVec::from(bytes.into_vec())
+    }
+}
+
+impl<'a> From<&'a [u8]> for HyperVec {
+    fn from(bytes: &'a [u8]) -> Self {
+        HyperVec::wrap_bytes(bytes)
+    }
+}
+
+impl From<HyperVec> for Vec<u8> {
+    /// Hands the allocation over to the Vec without copying. Views into a shared allocation, as well as allocations aligned for
+    /// types other than u8 (see [HyperVec::wrap]), are copied instead
+    fn from(mut hvec: HyperVec) -> Self {
+        if hvec.storage.is_some() || hvec.layout.align() != 1 || hvec.layout.size() == 0 {
+            return hvec.as_slice().to_vec();
+        }
+
+        let vec = unsafe { Vec::from_raw_parts(hvec.ptr, hvec.len, hvec.layout.size()) };
+        // The Vec now owns the allocation, so the HyperVec is left with an empty one to drop
+        hvec.layout = Layout::array::<u8>(0).unwrap();
+        hvec.ptr = crate::hypervec::allocate(hvec.layout, false);
+        hvec.len = 0;
+        vec
+    }
+}
+
+impl FromIterator<u8> for HyperVec {
+    fn from_iter<I: IntoIterator<Item=u8>>(iter: I) -> Self {
+        HyperVec::from(iter.into_iter().collect::<Vec<u8>>())
+    }
+}
+
+impl Extend<u8> for HyperVec {
+    fn extend<I: IntoIterator<Item=u8>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for byte in iter {
+            self.push(byte);
+        }
+    }
+}
+
+impl<'a> Extend<&'a u8> for HyperVec {
+    fn extend<I: IntoIterator<Item=&'a u8>>(&mut self, iter: I) {
+        Extend::<u8>::extend(self, iter.into_iter().cloned())
+    }
+}
+
+impl Clone for HyperVec {
+    /// The clone receives an allocation of its own (with the same capacity and alignment), alongside fresh read and write versions
+    /// under the same scheduling policy; visitors issued for self have no bearing upon the clone
+    fn clone(&self) -> Self {
+        let ptr = crate::hypervec::allocate(self.layout, false);
+        unsafe { std::ptr::copy_nonoverlapping(self.ptr, ptr, self.len) };
+
+        let mut hvec = HyperVec::from_raw_parts(ptr, self.len, self.layout).with_scheduling_policy(self.scheduling_policy());
+        hvec.cursor = self.cursor;
+        hvec.corrupt = self.corrupt;
+        hvec.endianness = self.endianness;
+        hvec.partition_map = self.partition_map.clone();
+        hvec
+    }
+}
+
+/// HyperVecs compare by their initialized bytes only; the cursor, versions and capacity are ignored
+impl PartialEq for HyperVec {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl Eq for HyperVec {}
+
+impl PartialEq<[u8]> for HyperVec {
+    fn eq(&self, other: &[u8]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl<'a> PartialEq<&'a [u8]> for HyperVec {
+    fn eq(&self, other: &&'a [u8]) -> bool {
+        self.as_slice() == *other
+    }
+}
+
+impl PartialEq<Vec<u8>> for HyperVec {
+    fn eq(&self, other: &Vec<u8>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl PartialOrd for HyperVec {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(Ord::cmp(self, other))
+    }
+}
+
+impl Ord for HyperVec {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_slice().cmp(other.as_slice())
+    }
+}
+
+impl Hash for HyperVec {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state)
+    }
+}
+
+impl Debug for HyperVec {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("HyperVec")
+            .field("len", &self.len)
+            .field("capacity", &self.capacity())
+            .field("cursor", &self.cursor)
+            .field("endianness", &self.endianness)
+            .field("bytes", &self.as_slice())
+            .finish()
+    }
+}
+
 /// The primary HyperVec is allowed to ship around between threads
 unsafe impl Send for HyperVec {}
 
@@ -66,7 +214,10 @@ unsafe impl Sync for HyperVec{}
 
 impl Drop for HyperVec {
     fn drop(&mut self) {
-        unsafe { std::alloc::Global.dealloc(NonNull::new(self.ptr).unwrap(), self.layout) }
+        // Views into a shared allocation leave the deallocation to the SharedStorage
+        if self.storage.is_none() && self.layout.size() != 0 {
+            unsafe { std::alloc::Global.dealloc(NonNull::new(self.ptr).unwrap(), self.layout) }
+        }
     }
 }
 
@@ -92,54 +243,135 @@ impl<'visit, T, E: AsRef<[u8]>> Try for MemoryResult<WriteVisitor<'visit, T>, Me
 impl Index<isize> for HyperVec {
     type Output = u8;
 
+    #[inline]
     fn index(&self, index: isize) -> &Self::Output {
-        unsafe { &*self.ptr.offset(index) }
+        match self.get(index) {
+            Some(byte) => byte,
+            None => panic!("index out of bounds: the len is {} but the index is {}", self.len, index)
+        }
+    }
+}
+
+impl IndexMut<isize> for HyperVec {
+    #[inline]
+    fn index_mut(&mut self, index: isize) -> &mut Self::Output {
+        let len = self.len;
+        match self.get_mut(index) {
+            Some(byte) => byte,
+            None => panic!("index out of bounds: the len is {} but the index is {}", len, index)
+        }
     }
 }
 
-impl Index<Range<isize>> for HyperVec {
-    type Output = [u8];
+/// Implements bounds-checked Index and IndexMut for each range type over isize
+macro_rules! impl_range_index {
+    ($($range:ty),*) => {
+        $(
+            impl Index<$range> for HyperVec {
+                type Output = [u8];
+
+                #[inline]
+                fn index(&self, index: $range) -> &Self::Output {
+                    match self.get_range(index.clone()) {
+                        Some(bytes) => bytes,
+                        None => panic!("range {:?} out of bounds for HyperVec of length {}", index, self.len)
+                    }
+                }
+            }
+
+            impl IndexMut<$range> for HyperVec {
+                #[inline]
+                fn index_mut(&mut self, index: $range) -> &mut Self::Output {
+                    let len = self.len;
+                    match self.get_range_mut(index.clone()) {
+                        Some(bytes) => bytes,
+                        None => panic!("range {:?} out of bounds for HyperVec of length {}", index, len)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_range_index!(Range<isize>, RangeFrom<isize>, RangeTo<isize>, RangeInclusive<isize>, RangeToInclusive<isize>, RangeFull);
+
+impl Deref for HyperVec {
+    type Target = [u8];
 
     #[inline]
-    fn index(&self, index: Range<isize>) -> &Self::Output {
-        unsafe { &*std::ptr::slice_from_raw_parts(&*self.ptr.offset(index.start), (index.end - index.start) as usize) }
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
     }
 }
 
-impl IndexMut<isize> for HyperVec {
+impl DerefMut for HyperVec {
     #[inline]
-    fn index_mut(&mut self, index: isize) -> &mut Self::Output {
-        unsafe { &mut *self.ptr.offset(index) }
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_mut_slice()
+    }
+}
+
+impl AsRef<[u8]> for HyperVec {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl AsMut<[u8]> for HyperVec {
+    #[inline]
+    fn as_mut(&mut self) -> &mut [u8] {
+        self.as_mut_slice()
     }
 }
 
-impl IndexMut<Range<isize>> for HyperVec {
-    fn index_mut(&mut self, index: Range<isize>) -> &mut Self::Output {
-        unsafe { &mut *std::ptr::slice_from_raw_parts_mut(&mut *self.ptr.offset(index.start), (index.end - index.start) as usize) }
+impl Borrow<[u8]> for HyperVec {
+    #[inline]
+    fn borrow(&self) -> &[u8] {
+        self.as_slice()
     }
 }
 
+/// Yields the bytes from the cursor onwards, advancing the cursor past each. Consuming the HyperVec in a `for` loop does the same
 impl Iterator for HyperVec {
     type Item = u8;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.cursor < self.len as isize {
-            let ret = self[self.cursor];
+        let byte = self.as_slice().get(self.cursor_position() as usize).cloned();
+        if byte.is_some() {
             self.cursor += 1;
-            Some(ret)
-        } else {
-            None
         }
+
+        byte
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.length().saturating_sub(self.cursor_position() as usize);
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a> IntoIterator for &'a HyperVec {
+    type Item = &'a u8;
+    type IntoIter = std::slice::Iter<'a, u8>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
     }
 }
 
-/// Unlike BufExtend, this necessariy requires the capacity to accomidate the placed bytes
+
+/// Unlike BufExtend, this necessariy requires the capacity to accomidate the placed bytes. Writing beyond the length (but within the capacity)
+/// increases the length
 impl BufMut for HyperVec {
     fn remaining_mut(&self) -> usize {
-        self.len - (self.cursor as usize)
+        self.capacity().saturating_sub(self.cursor as usize)
     }
     unsafe fn advance_mut(&mut self, cnt: usize) {
         self.advance_cursor_by(cnt);
+        if self.cursor as usize > self.len {
+            self.len = self.cursor as usize;
+        }
     }
     unsafe fn bytes_mut(&mut self) -> &mut [u8] {
         self.get_bytes_mut_cursor()
@@ -148,13 +380,17 @@ impl BufMut for HyperVec {
     #[inline]
     #[expand(depth = 5, expression = "*self.ptr.offset(p0 + {}) = slice[{}]")]
     fn put_slice(&mut self, slice: &[u8]) {
+        // Zeroes any gap between the length and the cursor, so that no uninitialized byte ends up below the length
+        self.prepare_write_at_cursor(slice.len());
         unsafe {
             debug_assert!(self.remaining_mut() >= slice.len());
             let p0 = self.cursor;
-            println!("putting w/ cursor pos {}", p0);
             let len = slice.len() as isize;
             match len {}
             self.cursor += len;
+            if self.cursor as usize > self.len {
+                self.len = self.cursor as usize;
+            }
         }
     }
 
@@ -164,18 +400,127 @@ impl BufMut for HyperVec {
     }
 }
 
+/// Reads at the cursor, so that a HyperVec drops into decoders written against `bytes` 0.4. The `_be` and `_le` readers keep
+/// their fixed byte order. The deprecated `get_u16::<T: ByteOrder>` family is shadowed by inherent methods of the same names
+/// which take no byte order and read in the buffer's [Endianness] instead; generic `B: Buf` code is unaffected. For fallible
+/// reads in the buffer's [Endianness], see [ByteReader]
+impl Buf for HyperVec {
+    fn remaining(&self) -> usize {
+        self.len.saturating_sub(std::cmp::max(self.cursor_position(), 0) as usize)
+    }
+
+    fn bytes(&self) -> &[u8] {
+        let start = self.len - self.remaining();
+        &self.as_slice()[start..]
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        assert!(cnt <= self.remaining(), "cannot advance past the end: {} remaining but advanced by {}", self.remaining(), cnt);
+        self.advance_cursor_by(cnt);
+    }
+}
+
+/// Generates the cursor-driven reads which follow the buffer's [Endianness] rather than a caller-supplied byte order
+macro_rules! impl_endian_gets {
+    ($($name:ident => $primitive:ty),*) => {
+        impl HyperVec {
+            $(
+                /// Reads the value at the cursor in the buffer's [Endianness], advancing the cursor past it. Panics if too few bytes remain, as [Buf] does
+                #[inline]
+                pub fn $name(&mut self) -> $primitive {
+                    self.get_endian::<$primitive>()
+                }
+            )*
+
+            #[inline]
+            fn get_endian<T: EndianPrimitive>(&mut self) -> T {
+                assert!(self.remaining() >= T::SIZE, "buffer has {} bytes remaining but {} were requested", self.remaining(), T::SIZE);
+                let value = T::from_endian_bytes(Buf::bytes(self), *self.get_endianness());
+                self.advance_cursor_by(T::SIZE);
+                value
+            }
+        }
+    };
+}
+
+impl_endian_gets!(get_u16 => u16, get_i16 => i16, get_u32 => u32, get_i32 => i32, get_u64 => u64, get_i64 => i64,
+                  get_u128 => u128, get_i128 => i128, get_f32 => f32, get_f64 => f64);
+
+impl std::io::Read for HyperVec {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let amt = std::cmp::min(buf.len(), self.remaining());
+        buf[..amt].copy_from_slice(&Buf::bytes(self)[..amt]);
+        self.advance_cursor_by(amt);
+        Ok(amt)
+    }
+}
+
+impl std::io::BufRead for HyperVec {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        Ok(Buf::bytes(self))
+    }
+
+    fn consume(&mut self, amt: usize) {
+        let amt = std::cmp::min(amt, self.remaining());
+        self.advance_cursor_by(amt);
+    }
+}
+
+impl std::io::Write for HyperVec {
+    /// Writes at the cursor, overwriting existing bytes and growing the buffer as needed
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.push_u8s(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl std::io::Seek for HyperVec {
+    /// Moves the cursor. Seeking before the start or past the initialized bytes is an [std::io::ErrorKind::InvalidInput] error
+    fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+        let len = self.length() as i128;
+        let target = match pos {
+            std::io::SeekFrom::Start(offset) => i128::from(offset),
+            std::io::SeekFrom::End(offset) => len + i128::from(offset),
+            std::io::SeekFrom::Current(offset) => self.cursor_position() as i128 + i128::from(offset)
+        };
+
+        if target < 0 || target > len {
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("cannot seek to {}; the length is {}", target, len)));
+        }
+
+        self.set_cursor_pos(target as isize);
+        Ok(target as u64)
+    }
+}
+
 /// Used to cast the internal of a HyperVec
 pub trait Castable {
-    /// Casts the underlying bytes to an immutable version of the the supplied type with checking alignment accompanied by a ReadVisitor
-    fn cast<Type: ?Sized>(&self) -> Result<ReadVisitor<Type>, MemError<&[u8]>>;
+    /// Casts the underlying bytes to an immutable version of the the supplied type with checking alignment accompanied by a ReadVisitor.
+    /// Returns [MemError::BAD_SIZE] if the buffer is shorter than the type, or [MemError::BAD_ALIGN] if the bytes are not aligned for it.
+    /// Since the writers ahead may move the buffer, both are checked again once the visitor is admitted: visiting then fails with
+    /// the same errors, and `read` returns None. Only [HyperPod] types may be cast safely; see [Castable::cast_unchecked] for any other
+    fn cast<Type: HyperPod>(&self) -> Result<ReadVisitor<Type>, MemError<&[u8]>>;
+    /// Like [Castable::cast], but under [SchedulingPolicy::Priority] the visitor waits in
+    /// lane `priority`, where higher lanes are admitted first. Other policies ignore the priority
+    fn cast_with_priority<Type: HyperPod>(&self, priority: u8) -> Result<ReadVisitor<Type>, MemError<&[u8]>>;
     /// Casts the underlying bytes to an immutable version of the the supplied type without checking alignment
     unsafe fn cast_unchecked<Type: ?Sized>(&self) -> &Type;
     /// Casts the underlying type to an array of the user-specified type. If the user is referencing an array of u16's, then when
     /// cast or cast_mut is called prior to this (to obtain the appropriate visitor), then the type paremeter should be "u16", but
     /// NOT "[u16]"
     unsafe fn cast_unchecked_array<Type: Sized>(&self) -> &[Type];
-    /// Casts the underlying bytes to a mutable version of the supplied type with checking alignment accompanied by a WriteVisitor
-    fn cast_mut<Type: ?Sized>(&mut self) -> Result<WriteVisitor<Type>, MemError<&[u8]>>;
+    /// Casts the underlying bytes to a mutable version of the supplied type with checking alignment accompanied by a WriteVisitor.
+    /// Fails as [Castable::cast] does, and like it, checks again once admitted, and after any `pre_alloc`, since either may move
+    /// the buffer. A `pre_alloc` always moves the bytes into an allocation aligned for the type. To write a `T` into bytes which do
+    /// not exist yet, extend the buffer before casting
+    fn cast_mut<Type: HyperPod>(&mut self) -> Result<WriteVisitor<Type>, MemError<&[u8]>>;
+    /// Like [Castable::cast_mut], but under [SchedulingPolicy::Priority] the visitor waits
+    /// in lane `priority`, where higher lanes are admitted first. Other policies ignore the priority
+    fn cast_mut_with_priority<Type: HyperPod>(&mut self, priority: u8) -> Result<WriteVisitor<Type>, MemError<&[u8]>>;
     /// Casts the underlying bytes to a mutable version of the the supplied type without checking alignment
     unsafe fn cast_unchecked_mut<Type: ?Sized>(&mut self) -> &mut Type;
 
@@ -183,13 +528,38 @@ pub trait Castable {
     /// cast or cast_mut is called prior to this (to obtain the appropriate visitor), then the type paremeter should be "u16", but
     /// NOT [u16]
     unsafe fn cast_unchecked_mut_array<Type: Sized>(&mut self) -> &mut [Type];
+
+    /// Casts the bytes within `range` to an immutable `Type` accompanied by a ReadVisitor which only locks that range. It waits
+    /// for the visitors issued before it, save for range visitors whose ranges it does not overlap or which only read. Returns
+    /// [MemError::OUT_OF_BOUNDS] if the range lies outside the buffer, and otherwise validates the range as [Castable::cast] does
+    fn cast_range<Type: HyperPod, R: RangeBounds<usize>>(&self, range: R) -> Result<ReadVisitor<Type>, MemError<&[u8]>>;
+    /// Casts the bytes within `range` to a mutable `Type` accompanied by a WriteVisitor which only locks that range. It waits
+    /// for the visitors issued before it, save for range visitors whose ranges it does not overlap, so disjoint sections of one
+    /// buffer may be written at once. The visitor cannot extend the buffer. Returns [MemError::OUT_OF_BOUNDS] if the range lies
+    /// outside the buffer, and otherwise validates the range as [Castable::cast_mut] does
+    fn cast_range_mut<Type: HyperPod, R: RangeBounds<usize>>(&mut self, range: R) -> Result<WriteVisitor<Type>, MemError<&[u8]>>;
+
+    /// Casts the `Type` stored at `offset` to an immutable version accompanied by a ReadVisitor, which only locks those bytes.
+    /// Intended for parsing a fixed header in place. Returns [MemError::OUT_OF_BOUNDS] if the `Type` would extend past the
+    /// buffer, or [MemError::BAD_ALIGN] if `offset` is not aligned for it
+    fn cast_at<Type: HyperPod>(&self, offset: usize) -> Result<ReadVisitor<Type>, MemError<&[u8]>>;
+    /// Like [Castable::cast_at], but casts to a mutable `Type` accompanied by a WriteVisitor
+    fn cast_mut_at<Type: HyperPod>(&mut self, offset: usize) -> Result<WriteVisitor<Type>, MemError<&[u8]>>;
+    /// Casts the `count` consecutive `Type`s stored at `offset` to an immutable slice, obtained through the ReadVisitor's
+    /// `read_array`. Intended for parsing a typed payload which follows a header. Fails as [Castable::cast_at] does
+    fn cast_slice_at<Type: HyperPod>(&self, offset: usize, count: usize) -> Result<ReadVisitor<Type>, MemError<&[u8]>>;
+    /// Like [Castable::cast_slice_at], but the mutable slice is obtained through the WriteVisitor's `write_array`
+    fn cast_slice_mut_at<Type: HyperPod>(&mut self, offset: usize, count: usize) -> Result<WriteVisitor<Type>, MemError<&[u8]>>;
 }
 
 
 impl Castable for HyperVec {
-    fn cast<Type: ?Sized>(&self) -> Result<ReadVisitor<Type>, MemError<&[u8]>> {
-        //println!("{} {} | {} {}", std::mem::align_of::<&Type>(), self.layout.align(), std::mem::size_of::<&Type>(), self.layout.size());
-        Ok(ReadVisitor::new(self as *const Self as *mut Self, self.get_write_version()))
+    fn cast<Type: HyperPod>(&self) -> Result<ReadVisitor<Type>, MemError<&[u8]>> {
+        self.cast_with_priority::<Type>(0)
+    }
+
+    fn cast_with_priority<Type: HyperPod>(&self, priority: u8) -> Result<ReadVisitor<Type>, MemError<&[u8]>> {
+        unsafe { HyperVec::issue_read::<Type>(self as *const Self as *mut Self, priority) }
     }
 
     unsafe fn cast_unchecked<Type: ?Sized>(&self) -> &Type {
@@ -203,9 +573,12 @@ impl Castable for HyperVec {
         &*std::ptr::slice_from_raw_parts(base_ptr, self.len / std::mem::size_of::<Type>())
     }
 
-    fn cast_mut<Type: ?Sized>(&mut self) -> Result<WriteVisitor<Type>, MemError<&[u8]>> {
-        //println!("{} {} | {} {}", std::mem::align_of::<&Type>(), self.layout.align(), std::mem::size_of::<&Type>(), self.layout.size());
-        Ok(WriteVisitor::new(&mut *self as *mut Self, self.get_write_version()))
+    fn cast_mut<Type: HyperPod>(&mut self) -> Result<WriteVisitor<Type>, MemError<&[u8]>> {
+        self.cast_mut_with_priority::<Type>(0)
+    }
+
+    fn cast_mut_with_priority<Type: HyperPod>(&mut self, priority: u8) -> Result<WriteVisitor<Type>, MemError<&[u8]>> {
+        unsafe { HyperVec::issue_write::<Type>(self as *mut Self, priority) }
     }
 
     unsafe fn cast_unchecked_mut<Type: ?Sized>(&mut self) -> &mut Type {
@@ -218,8 +591,233 @@ impl Castable for HyperVec {
         let base_ptr = std::mem::transmute_copy::<*mut u8, *mut Type>(&self.ptr);
         &mut *std::ptr::slice_from_raw_parts_mut(base_ptr, self.len / std::mem::size_of::<Type>())
     }
+
+    fn cast_range<Type: HyperPod, R: RangeBounds<usize>>(&self, range: R) -> Result<ReadVisitor<Type>, MemError<&[u8]>> {
+        unsafe { HyperVec::issue_range_read::<Type, R>(self as *const Self as *mut Self, range) }
+    }
+
+    fn cast_range_mut<Type: HyperPod, R: RangeBounds<usize>>(&mut self, range: R) -> Result<WriteVisitor<Type>, MemError<&[u8]>> {
+        unsafe { HyperVec::issue_range_write::<Type, R>(self as *mut Self, range) }
+    }
+
+    fn cast_at<Type: HyperPod>(&self, offset: usize) -> Result<ReadVisitor<Type>, MemError<&[u8]>> {
+        self.cast_slice_at::<Type>(offset, 1)
+    }
+
+    fn cast_mut_at<Type: HyperPod>(&mut self, offset: usize) -> Result<WriteVisitor<Type>, MemError<&[u8]>> {
+        self.cast_slice_mut_at::<Type>(offset, 1)
+    }
+
+    fn cast_slice_at<Type: HyperPod>(&self, offset: usize, count: usize) -> Result<ReadVisitor<Type>, MemError<&[u8]>> {
+        let span = self.span_of::<Type>(offset, count)?;
+        self.cast_range::<Type, _>(span)
+    }
+
+    fn cast_slice_mut_at<Type: HyperPod>(&mut self, offset: usize, count: usize) -> Result<WriteVisitor<Type>, MemError<&[u8]>> {
+        let span = self.span_of::<Type>(offset, count)?;
+        self.cast_range_mut::<Type, _>(span)
+    }
+}
+
+impl HyperVec {
+    /// Issues a ReadVisitor over the whole buffer at `hvec`. Only a shared borrow of the HyperVec is ever taken, so that
+    /// [SharedHyperVec] may issue visitors while other threads hold theirs
+    pub(crate) unsafe fn issue_read<'a, Type: HyperPod>(hvec: *mut HyperVec, priority: u8) -> InformationResult<'a, ReadVisitor<'a, Type>> {
+        let shared = &*hvec;
+        shared.check_fit::<Type>(0, shared.len)?;
+        Ok(ReadVisitor::new(hvec, Position::whole(shared.enter(TicketKind::Read, priority))))
+    }
+
+    /// Issues a WriteVisitor over the whole buffer at `hvec`. The visitor writes through `hvec` itself rather than through a
+    /// `&mut HyperVec`, which would alias the borrows held by other handles and visitors
+    pub(crate) unsafe fn issue_write<'a, Type: HyperPod>(hvec: *mut HyperVec, priority: u8) -> InformationResult<'a, WriteVisitor<'a, Type>> {
+        let shared = &*hvec;
+        shared.check_fit::<Type>(0, shared.len)?;
+        Ok(WriteVisitor::new(hvec, Position::whole(shared.enter(TicketKind::Write, priority))))
+    }
+
+    /// Issues a ReadVisitor over `range` of the buffer at `hvec`, borrowing the HyperVec only immutably as [HyperVec::issue_read] does
+    pub(crate) unsafe fn issue_range_read<'a, Type: HyperPod, R: RangeBounds<usize>>(hvec: *mut HyperVec, range: R) -> InformationResult<'a, ReadVisitor<'a, Type>> {
+        let shared = &*hvec;
+        let (start, end) = shared.checked_region(&range)?;
+        shared.check_fit::<Type>(start, end - start)?;
+        Ok(ReadVisitor::new(hvec, shared.enter_range(start, end, false)))
+    }
+
+    /// Issues a WriteVisitor over `range` of the buffer at `hvec`, borrowing the HyperVec only immutably as [HyperVec::issue_write] does
+    pub(crate) unsafe fn issue_range_write<'a, Type: HyperPod, R: RangeBounds<usize>>(hvec: *mut HyperVec, range: R) -> InformationResult<'a, WriteVisitor<'a, Type>> {
+        let shared = &*hvec;
+        let (start, end) = shared.checked_region(&range)?;
+        shared.check_fit::<Type>(start, end - start)?;
+        Ok(WriteVisitor::new(hvec, shared.enter_range(start, end, true)))
+    }
+
+    /// Returns the range spanned by `count` consecutive `Type`s starting at `offset`, or [MemError::OUT_OF_BOUNDS] if it cannot
+    /// be represented
+    fn span_of<Type>(&self, offset: usize, count: usize) -> InformationResult<'static, Range<usize>> {
+        match std::mem::size_of::<Type>().checked_mul(count).and_then(|size| offset.checked_add(size)) {
+            Some(end) => Ok(offset..end),
+            None => MemError::throw_out_of_bounds(offset as isize, usize::max_value(), self.len)
+        }
+    }
+
+    /// Resolves `range` into (start, end) offsets, or returns [MemError::OUT_OF_BOUNDS] if it lies outside the buffer
+    fn checked_region<R: RangeBounds<usize>>(&self, range: &R) -> InformationResult<'static, (usize, usize)> {
+        crate::util::checked_range(range, self.len).or_else(|(start, end)| MemError::throw_out_of_bounds(start as isize, end.saturating_sub(start), self.len))
+    }
+
+    /// Checks that the `len` bytes at `offset` are enough to hold a `Type`, and aligned for it
+    pub(crate) fn check_fit<Type>(&self, offset: usize, len: usize) -> InformationResult<'static, ()> {
+        let size = std::mem::size_of::<Type>();
+        if size > len {
+            return MemError::throw_bad_size(size, len);
+        }
+
+        let align = std::mem::align_of::<Type>();
+        let address = self.ptr as usize + offset;
+        if address % align != 0 {
+            return MemError::throw_bad_align(align, 1 << address.trailing_zeros());
+        }
+
+        Ok(())
+    }
+}
+
+
+/// A primitive which can be decoded from, and encoded into, bytes of either [Endianness]
+pub trait EndianPrimitive: Sized + Copy {
+    /// The number of bytes the primitive occupies
+    const SIZE: usize;
+    /// Decodes the primitive from the first `SIZE` bytes of `bytes`. Panics if fewer bytes are supplied
+    fn from_endian_bytes(bytes: &[u8], endianness: Endianness) -> Self;
+    /// Encodes the primitive into the first `SIZE` bytes of `out`. Panics if fewer bytes are supplied
+    fn to_endian_bytes(self, out: &mut [u8], endianness: Endianness);
 }
 
+/// Implements [EndianPrimitive] for integers, which all expose {from,to}_{be,le}_bytes
+macro_rules! impl_endian_primitive {
+    ($($primitive:ty),*) => {
+        $(
+            impl EndianPrimitive for $primitive {
+                const SIZE: usize = std::mem::size_of::<$primitive>();
+
+                #[inline]
+                fn from_endian_bytes(bytes: &[u8], endianness: Endianness) -> Self {
+                    let mut raw = [0u8; std::mem::size_of::<$primitive>()];
+                    raw.copy_from_slice(&bytes[..Self::SIZE]);
+                    match endianness {
+                        Endianness::BE => <$primitive>::from_be_bytes(raw),
+                        Endianness::LE => <$primitive>::from_le_bytes(raw)
+                    }
+                }
+
+                #[inline]
+                fn to_endian_bytes(self, out: &mut [u8], endianness: Endianness) {
+                    let raw = match endianness {
+                        Endianness::BE => self.to_be_bytes(),
+                        Endianness::LE => self.to_le_bytes()
+                    };
+                    out[..Self::SIZE].copy_from_slice(&raw);
+                }
+            }
+        )*
+    };
+}
+
+/// Implements [EndianPrimitive] for floats by way of their bit patterns
+macro_rules! impl_endian_float {
+    ($($float:ty => $bits:ty),*) => {
+        $(
+            impl EndianPrimitive for $float {
+                const SIZE: usize = std::mem::size_of::<$float>();
+
+                #[inline]
+                fn from_endian_bytes(bytes: &[u8], endianness: Endianness) -> Self {
+                    <$float>::from_bits(<$bits>::from_endian_bytes(bytes, endianness))
+                }
+
+                #[inline]
+                fn to_endian_bytes(self, out: &mut [u8], endianness: Endianness) {
+                    self.to_bits().to_endian_bytes(out, endianness)
+                }
+            }
+        )*
+    };
+}
+
+impl_endian_primitive!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+impl_endian_float!(f32 => u32, f64 => u64);
+
+/// Generates the named readers of [ByteReader] on top of its generic methods
+macro_rules! byte_reader_methods {
+    ($($primitive:ty => $read:ident, $peek:ident, $read_at:ident);*) => {
+        $(
+            /// Reads the value at the cursor in the internal buffer's set order, then advances the cursor past it
+            fn $read(&mut self) -> InformationResult<'static, $primitive> {
+                self.read_as::<$primitive>()
+            }
+
+            /// Reads the value at the cursor in the internal buffer's set order without advancing the cursor
+            fn $peek(&self) -> InformationResult<'static, $primitive> {
+                self.peek_as::<$primitive>()
+            }
+
+            /// Reads the value at `offset` from the initial pointer in the internal buffer's set order. The cursor is left untouched
+            fn $read_at(&self, offset: usize) -> InformationResult<'static, $primitive> {
+                self.read_as_at::<$primitive>(offset)
+            }
+        )*
+    };
+}
+
+/// The reading counterpart to [BytePusher]. Every read decodes with the internal buffer's set order, and returns
+/// [MemError::OUT_OF_BOUNDS] instead of reading past the initialized bytes
+pub trait ByteReader {
+    /// Decodes a `T` at the cursor, then advances the cursor by size_of::<T>()
+    fn read_as<T: EndianPrimitive>(&mut self) -> InformationResult<'static, T>;
+    /// Decodes a `T` at the cursor without advancing the cursor
+    fn peek_as<T: EndianPrimitive>(&self) -> InformationResult<'static, T>;
+    /// Decodes a `T` at `offset` from the initial pointer. The cursor is left untouched
+    fn read_as_at<T: EndianPrimitive>(&self, offset: usize) -> InformationResult<'static, T>;
+
+    byte_reader_methods!(u8 => read_u8, peek_u8, read_u8_at;
+                         u16 => read_u16, peek_u16, read_u16_at;
+                         u32 => read_u32, peek_u32, read_u32_at;
+                         u64 => read_u64, peek_u64, read_u64_at;
+                         u128 => read_u128, peek_u128, read_u128_at;
+                         i8 => read_i8, peek_i8, read_i8_at;
+                         i16 => read_i16, peek_i16, read_i16_at;
+                         i32 => read_i32, peek_i32, read_i32_at;
+                         i64 => read_i64, peek_i64, read_i64_at;
+                         i128 => read_i128, peek_i128, read_i128_at;
+                         f32 => read_f32, peek_f32, read_f32_at;
+                         f64 => read_f64, peek_f64, read_f64_at);
+}
+
+impl ByteReader for HyperVec {
+    fn read_as<T: EndianPrimitive>(&mut self) -> InformationResult<'static, T> {
+        let value = self.peek_as::<T>()?;
+        self.advance_cursor_by(T::SIZE);
+        Ok(value)
+    }
+
+    fn peek_as<T: EndianPrimitive>(&self) -> InformationResult<'static, T> {
+        let cursor = self.cursor_position();
+        if cursor < 0 {
+            return MemError::throw_out_of_bounds(cursor, T::SIZE, self.length());
+        }
+
+        self.read_as_at::<T>(cursor as usize)
+    }
+
+    fn read_as_at<T: EndianPrimitive>(&self, offset: usize) -> InformationResult<'static, T> {
+        let len = self.length();
+        match offset.checked_add(T::SIZE) {
+            Some(end) if end <= len => Ok(T::from_endian_bytes(&self.as_slice()[offset..end], *self.get_endianness())),
+            _ => MemError::throw_out_of_bounds(offset as isize, T::SIZE, len)
+        }
+    }
+}
 
 /// Byte-order aware wrapper for data allocation within a [HyperVec]
 pub trait ByteWrapper {
@@ -270,14 +868,13 @@ impl ByteWrapper for HyperVec {
         let t = t.as_ref();
         let len = t.len();
         let layout = Layout::array::<u8>(len).unwrap();
-        println!("Align: {}, Size: {}", std::mem::align_of_val(&t), std::mem::size_of_val(&t));
 
         let ptr0 = (&*t as *const [u8]) as *const u8;
 
-        let ptr = unsafe { std::alloc::alloc(layout) };
+        let ptr = crate::hypervec::allocate(layout, false);
         unsafe { std::ptr::copy_nonoverlapping(ptr0, ptr, layout.size()) };
 
-        Self {ptr, len, cursor: 0, read_version: AtomicUsize::new(0), write_version: AtomicUsize::new(0), corrupt: false, layout, endianness: Endianness::target(), partition_map: None}
+        Self::from_raw_parts(ptr, len, layout)
     }
 
     /// Wraps around an array of u16's, and returns a vector comprised of fundamental u8 bytes
@@ -427,11 +1024,10 @@ impl ByteWrapper for HyperVec {
 }
 
 /// Unlike BufMut, BytePusher's impl's will resize HyperVec's buffer before pushing the bytes inwards
-/// WARNING: This does not do any Bounds' checking! Make sure that the space does indeed require to be pushed.
-/// This will load data at the cursor position; put_u8 (or putter functions in general) are what ought to be used
-/// when the vector is pre-allocated
+/// This will load data at the cursor position, growing the capacity geometrically (see [HyperVec::reserve]) whenever the
+/// bytes do not fit; put_u8 (or putter functions in general) are what ought to be used when the vector is pre-allocated
 pub trait BytePusher {
-    /// Extends the underlying buffer by len * size_of::<u8>(), and pushes each byte thereon (this is the only place where extension occurs; the others helper subroutines simply push converted bytes into here)
+    /// Reserves len * size_of::<u8>() bytes at the cursor, and pushes each byte thereon
     fn push_u8s<T: AsRef<[u8]>>(&mut self, t: T);
     /// Extends the underlying buffer by len * size_of::<u16>(), Converts each u16 into the internal buffer's set order, then pushes each byte into the buffer
     fn push_u16s<T: AsRef<[u16]>>(&mut self, t: T);
@@ -460,13 +1056,13 @@ impl BytePusher for HyperVec {
     #[inline]
     fn push_u8s<T: AsRef<[u8]>>(&mut self, t: T) {
         let t  = t.as_ref();
-        self.extend(t.len());
+        self.prepare_write_at_cursor(t.len());
         self.put_slice(t);
     }
 
     fn push_u16s<T: AsRef<[u16]>>(&mut self, t: T) {
         let t  = t.as_ref();
-        self.extend(t.len() * 2);
+        self.prepare_write_at_cursor(t.len() * 2);
 
         match self.endianness {
             Endianness::BE => {
@@ -485,7 +1081,7 @@ impl BytePusher for HyperVec {
 
     fn push_u32s<T: AsRef<[u32]>>(&mut self, t: T) {
         let t  = t.as_ref();
-        self.extend(t.len() * 4);
+        self.prepare_write_at_cursor(t.len() * 4);
 
         match self.endianness {
             Endianness::BE => {
@@ -504,7 +1100,7 @@ impl BytePusher for HyperVec {
 
     fn push_u64s<T: AsRef<[u64]>>(&mut self, t: T) {
         let t  = t.as_ref();
-        self.extend(t.len() * 8);
+        self.prepare_write_at_cursor(t.len() * 8);
 
         match self.endianness {
             Endianness::BE => {
@@ -523,7 +1119,7 @@ impl BytePusher for HyperVec {
 
     fn push_u128s<T: AsRef<[u128]>>(&mut self, t: T) {
         let t  = t.as_ref();
-        self.extend(t.len() * 16);
+        self.prepare_write_at_cursor(t.len() * 16);
 
         match self.endianness {
             Endianness::BE => {
@@ -542,7 +1138,7 @@ impl BytePusher for HyperVec {
 
     fn push_i8s<T: AsRef<[i8]>>(&mut self, t: T) {
         let t  = t.as_ref();
-        self.extend(t.len());
+        self.prepare_write_at_cursor(t.len());
 
         match self.endianness {
             Endianness::BE => {
@@ -561,7 +1157,7 @@ impl BytePusher for HyperVec {
 
     fn push_i16s<T: AsRef<[i16]>>(&mut self, t: T) {
         let t  = t.as_ref();
-        self.extend(t.len() * 2);
+        self.prepare_write_at_cursor(t.len() * 2);
 
         match self.endianness {
             Endianness::BE => {
@@ -580,7 +1176,7 @@ impl BytePusher for HyperVec {
 
     fn push_i32s<T: AsRef<[i32]>>(&mut self, t: T) {
         let t  = t.as_ref();
-        self.extend(t.len() * 4);
+        self.prepare_write_at_cursor(t.len() * 4);
 
         match self.endianness {
             Endianness::BE => {
@@ -599,7 +1195,7 @@ impl BytePusher for HyperVec {
 
     fn push_i64s<T: AsRef<[i64]>>(&mut self, t: T) {
         let t  = t.as_ref();
-        self.extend(t.len() * 8);
+        self.prepare_write_at_cursor(t.len() * 8);
 
         match self.endianness {
             Endianness::BE => {
@@ -618,7 +1214,7 @@ impl BytePusher for HyperVec {
 
     fn push_i128s<T: AsRef<[i128]>>(&mut self, t: T) {
         let t  = t.as_ref();
-        self.extend(t.len() * 16);
+        self.prepare_write_at_cursor(t.len() * 16);
 
         match self.endianness {
             Endianness::BE => {
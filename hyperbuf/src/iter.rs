@@ -0,0 +1,48 @@
+/*
+ * Copyright (c) 2019. The information/code/data contained within this file and all other files with the same copyright are protected under US Statutes. You must have explicit written access by Thomas P. Braun in order to access, view, modify, alter, or apply this code in any context commercial or non-commercial. If you have this code but were not given explicit written access by Thomas P. Braun, you must destroy the information herein for legal safety. You agree that if you apply the concepts herein without any written access, Thomas P. Braun will seek the maximum possible legal retribution.
+ */
+
+use std::marker::PhantomData;
+use std::slice::ChunksExact;
+
+use crate::hypervec::Endianness;
+use crate::impls::EndianPrimitive;
+
+/// Decodes consecutive values of type `T` from the bytes of a [HyperVec] in the buffer's [Endianness]. Trailing bytes too few
+/// to form a whole `T` are skipped
+pub struct TypedIter<'a, T> {
+    chunks: ChunksExact<'a, u8>,
+    endianness: Endianness,
+    _phantom: PhantomData<T>
+}
+
+impl<'a, T: EndianPrimitive> TypedIter<'a, T> {
+    pub(crate) fn new(bytes: &'a [u8], endianness: Endianness) -> Self {
+        Self { chunks: bytes.chunks_exact(T::SIZE), endianness, _phantom: PhantomData }
+    }
+}
+
+impl<'a, T: EndianPrimitive> Iterator for TypedIter<'a, T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let endianness = self.endianness;
+        self.chunks.next().map(|chunk| T::from_endian_bytes(chunk, endianness))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chunks.size_hint()
+    }
+}
+
+impl<'a, T: EndianPrimitive> DoubleEndedIterator for TypedIter<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let endianness = self.endianness;
+        self.chunks.next_back().map(|chunk| T::from_endian_bytes(chunk, endianness))
+    }
+}
+
+impl<'a, T: EndianPrimitive> ExactSizeIterator for TypedIter<'a, T> {}
@@ -16,14 +16,48 @@ warnings
 
 /// Import everything herein to gain access to the HyperVec and all its associated structures, subroutines, and implementations
 pub mod prelude {
-    pub use crate::hypervec::{Endianness, HyperVec};
+    pub use crate::hypervec::{Endianness, HyperVec, SchedulingPolicy};
+    pub use crate::hyperbytes::HyperBytes;
+    pub use crate::iter::TypedIter;
+    pub use crate::guard::{ReadGuard, WriteGuard};
+    pub use crate::shared::SharedHyperVec;
+    pub use crate::pod::HyperPod;
+    pub use crate::unaligned::Unaligned;
+    pub use crate::endian::*;
     pub use crate::impls::*;
     pub use crate::results::*;
+    #[cfg(feature = "stats")]
+    pub use crate::stats::{HyperVecStats, WaitStats};
 }
 
 /// A memory primitive
 pub mod hypervec;
 
+/// An immutable, reference-counted byte buffer produced by freezing a HyperVec
+pub mod hyperbytes;
+
+/// Iterators over the bytes of a HyperVec
+pub mod iter;
+
+/// RAII guards which hold a visitor's ticket for as long as they live
+pub mod guard;
+
+/// A reference-counted HyperVec handle whose guards may cross threads
+pub mod shared;
+
+/// The marker trait, and its derive, for types which may be safely cast from bytes
+pub mod pod;
+
+/// A wrapper for reading and writing values at any alignment
+pub mod unaligned;
+
+/// Integer and float field types with a fixed byte order, for declaring wire formats inside structs cast from a HyperVec
+pub mod endian;
+
+/// Counters describing how a HyperVec is contended for, enabled by the `stats` feature
+#[cfg(feature = "stats")]
+pub mod stats;
+
 pub(crate) mod results;
 
 #[macro_use]
@@ -31,6 +65,8 @@ extern crate hyperbuf_derive;
 
 pub(crate) mod util;
 
+pub(crate) mod scheduler;
+
 /// provides useful implementations for HyperVec
 pub mod impls;
 
@@ -88,7 +88,7 @@ impl PartitionMap {
         // TODO: Below may cause a bug, because does n * size_of may not be accurate
         let layout = Layout::array::<RelativeObjectLocation>(std::mem::size_of::<RelativeObjectLocation>()).unwrap();
         //println!("sz {} al {}", std::mem::size_of::<RelativeObjectLocation>(), std::mem::align_of::<RelativeObjectLocation>());
-        let ptr = unsafe {std::alloc::alloc(layout)} as *mut RelativeObjectLocation;
+        let ptr = crate::hypervec::allocate(layout, false) as *mut RelativeObjectLocation;
         //let m = TypeId {t: 88};
         let object_count = 0;
         Self {ptr, object_count, layout}
@@ -154,6 +154,35 @@ impl PartitionMap {
         std::ptr::read_unaligned(self.ptr.offset(idx));
     }
 
+    /// Brings the tracked locations in line with an edit of the underlying buffer, wherein `removed` bytes at `at` were replaced
+    /// by `inserted` bytes. Entries past the edited region shift by the difference, while entries overlapping the edited region no longer
+    /// describe a whole object and are removed from the map
+    pub fn adjust_for_edit(&mut self, at: isize, removed: isize, inserted: isize) {
+        let delta = inserted - removed;
+        let mut kept = 0;
+
+        for idx in 0..self.object_count {
+            let location = self[idx].location;
+            let keep = if location + self[idx].length <= at {
+                true
+            } else if location >= at + removed {
+                self[idx].location += delta;
+                true
+            } else {
+                false
+            };
+
+            if keep {
+                if kept != idx {
+                    unsafe { std::ptr::copy_nonoverlapping(self.ptr.offset(idx), self.ptr.offset(kept), 1) };
+                }
+                kept += 1;
+            }
+        }
+
+        self.object_count = kept;
+    }
+
     /// Returns the object at idc
     pub unsafe fn retrieve(&self, idx: &usize) -> &RelativeObjectLocation {
         &self[*idx as isize]
@@ -185,6 +214,16 @@ impl Display for PartitionMap {
     }
 }
 
+impl Clone for PartitionMap {
+    // The layout is that of the locations, so the allocation is aligned for them
+    #[allow(clippy::cast_ptr_alignment)]
+    fn clone(&self) -> Self {
+        let ptr = crate::hypervec::allocate(self.layout, false) as *mut RelativeObjectLocation;
+        unsafe { std::ptr::copy_nonoverlapping(self.ptr, ptr, self.object_count as usize) };
+        Self { ptr, object_count: self.object_count, layout: self.layout }
+    }
+}
+
 impl Default for PartitionMap {
     fn default() -> Self {
         Self::new()
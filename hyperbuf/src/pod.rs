@@ -0,0 +1,32 @@
+/*
+ * Copyright (c) 2019. The information/code/data contained within this file and all other files with the same copyright are protected under US Statutes. You must have explicit written access by Thomas P. Braun in order to access, view, modify, alter, or apply this code in any context commercial or non-commercial. If you have this code but were not given explicit written access by Thomas P. Braun, you must destroy the information herein for legal safety. You agree that if you apply the concepts herein without any written access, Thomas P. Braun will seek the maximum possible legal retribution.
+ */
+
+pub use hyperbuf_derive::HyperPod;
+
+/// Marks a type whose values may be read straight out of, and written straight into, the bytes of a HyperVec. Safe casts
+/// such as [Castable::cast] only accept types implementing it.
+///
+/// # Safety
+/// Every pattern of `size_of::<Self>()` bytes must be a valid value, so the type may not hold references, pointers to owned
+/// memory, `bool`s, `char`s, enums, or padding bytes (which are uninitialized). Rather than implementing it by hand, derive it
+/// on a `#[repr(C)]`, `#[repr(transparent)]` or `#[repr(packed)]` struct with `#[derive(HyperPod)]`, which checks each of
+/// these requirements at compile time
+pub unsafe trait HyperPod: Copy + 'static {}
+
+macro_rules! impl_pod {
+    ($($ty:ty),*) => {
+        $(unsafe impl HyperPod for $ty {})*
+    };
+}
+
+macro_rules! impl_pod_arrays {
+    ($($len:expr),*) => {
+        $(unsafe impl<T: HyperPod> HyperPod for [T; $len] {})*
+    };
+}
+
+impl_pod!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+impl_pod_arrays!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32,
+    48, 64, 96, 128, 256, 512, 1024, 2048, 4096);
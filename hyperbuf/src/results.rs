@@ -15,10 +15,33 @@ pub enum MemError<'a, E: AsRef<[u8]>> {
     OUT_OF_SYNC,
     /// Not ready (for polling)
     NOT_READY,
-    /// #
-    BAD_ALIGN(E),
+    /// The ticket was not admitted before its deadline
+    TIMED_OUT,
+    /// The bytes were not aligned for the type they were to be cast to
+    BAD_ALIGN {
+        /// The alignment the type requires
+        expected: usize,
+        /// The largest alignment the address satisfies
+        actual: usize
+    },
+    /// There were too few bytes to hold the type they were to be cast to
+    BAD_SIZE {
+        /// The size of the type
+        expected: usize,
+        /// The number of bytes available
+        actual: usize
+    },
     /// A generic error message
     GENERIC(E),
+    /// An access extended past the initialized bytes
+    OUT_OF_BOUNDS {
+        /// The offset at which the access began
+        offset: isize,
+        /// The number of bytes the access required
+        requested: usize,
+        /// The number of initialized bytes available
+        len: usize
+    },
     /// #
     _phantom(&'a E)
 }
@@ -31,8 +54,18 @@ impl<'a: 'static, E: 'a +  AsRef<[u8]> + 'a> MemError<'a, E> {
     }
 
     /// #
-    pub fn throw_bad_align<U>(data: E) -> Result<U, Self> {
-        Err(MemError::BAD_ALIGN(data))
+    pub fn throw_bad_align<U>(expected: usize, actual: usize) -> Result<U, Self> {
+        Err(MemError::BAD_ALIGN { expected, actual })
+    }
+
+    /// #
+    pub fn throw_bad_size<U>(expected: usize, actual: usize) -> Result<U, Self> {
+        Err(MemError::BAD_SIZE { expected, actual })
+    }
+
+    /// #
+    pub fn throw_out_of_bounds<U>(offset: isize, requested: usize, len: usize) -> Result<U, Self> {
+        Err(MemError::OUT_OF_BOUNDS { offset, requested, len })
     }
 
     /// #
@@ -50,6 +83,10 @@ impl<'a: 'static, E: 'a +  AsRef<[u8]> + 'a> MemError<'a, E> {
         std::io::Error::new(std::io::ErrorKind::Other, MemError::GENERIC(data))
     }
 
+}
+
+// Formatting is available at any lifetime, so that results borrowing a HyperVec can still be unwrapped
+impl<'a, E: AsRef<[u8]>> MemError<'a, E> {
     fn printf(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
         match self {
             MemError::CORRUPT(t) => {
@@ -64,13 +101,25 @@ impl<'a: 'static, E: 'a +  AsRef<[u8]> + 'a> MemError<'a, E> {
                 write!(f, "[MemoryError] Not ready")
             },
 
-            MemError::BAD_ALIGN(t) => {
-                write!(f, "[MemoryError] Bad Align. {}", String::from_utf8_lossy(t.as_ref()))
+            MemError::TIMED_OUT => {
+                write!(f, "[MemoryError] Timed out")
+            },
+
+            MemError::BAD_ALIGN { expected, actual } => {
+                write!(f, "[MemoryError] Bad Align. Expected an alignment of {}, but the address is aligned to {}", expected, actual)
+            }
+
+            MemError::BAD_SIZE { expected, actual } => {
+                write!(f, "[MemoryError] Bad Size. Expected {} bytes, but only {} are available", expected, actual)
             }
 
             MemError::GENERIC(msg) => {
                 write!(f, "[MemoryError] {}", String::from_utf8_lossy((*msg.as_ref()).as_ref()))
             }
+
+            MemError::OUT_OF_BOUNDS { offset, requested, len } => {
+                write!(f, "[MemoryError] Out of bounds. {} bytes requested at offset {}, but the length is {}", requested, offset, len)
+            }
             _ => {write!(f, "[MemoryError] Undefined")}
         }
     }
@@ -94,24 +143,36 @@ impl<'a: 'static, E: 'a +  AsRef<[u8]> + 'a> MemError<'a, E> {
             MemError::GENERIC(_) => {
                 3
             }
+
+            MemError::OUT_OF_BOUNDS { .. } => {
+                5
+            }
+
+            MemError::TIMED_OUT => {
+                6
+            }
+
+            MemError::BAD_SIZE { .. } => {
+                7
+            }
             _ => {4}
         }
     }
 }
 
-impl<'a: 'static, E: AsRef<[u8]>> Display for MemError<'a, E> {
+impl<'a, E: AsRef<[u8]>> Display for MemError<'a, E> {
     fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
         self.printf(f)
     }
 }
 
-impl<'a: 'static, E: AsRef<[u8]>> Debug for MemError<'a, E> {
+impl<'a, E: AsRef<[u8]>> Debug for MemError<'a, E> {
     fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
         self.printf(f)
     }
 }
 
-impl<'a: 'static, E: AsRef<[u8]>> Error for MemError<'a, E> {}
+impl<'a, E: AsRef<[u8]>> Error for MemError<'a, E> {}
 unsafe impl<'a, E: AsRef<[u8]>> Send for MemError<'a, E> {}
 unsafe impl<'a, E: AsRef<[u8]>> Sync for MemError<'a, E> {}
 
@@ -0,0 +1,535 @@
+/*
+ * Copyright (c) 2019. The information/code/data contained within this file and all other files with the same copyright are protected under US Statutes. You must have explicit written access by Thomas P. Braun in order to access, view, modify, alter, or apply this code in any context commercial or non-commercial. If you have this code but were not given explicit written access by Thomas P. Braun, you must destroy the information herein for legal safety. You agree that if you apply the concepts herein without any written access, Thomas P. Braun will seek the maximum possible legal retribution.
+ */
+
+use std::cell::Cell;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::task::{Context, Poll, Waker};
+use std::time::{Duration, Instant};
+
+use futures::task::ArcWake;
+use parking_lot::{Condvar, Mutex};
+
+use crate::hypervec::HyperVec;
+use crate::results::{InformationResult, MemError};
+
+/// Holds the wakers of visitors whose tickets have not yet reached the front of the line, so that the visitor
+/// finishing ahead of them can wake them instead of leaving them pending forever
+pub(crate) struct WakerRegistry<K> {
+    waiting: Mutex<Vec<(K, Waker)>>
+}
+
+impl<K: Copy> WakerRegistry<K> {
+    /// Creates an empty registry
+    pub(crate) fn new() -> Self {
+        Self { waiting: Mutex::new(Vec::new()) }
+    }
+
+    /// Stores `waker` under `key`. Re-registering the same task for the same key is a no-op
+    pub(crate) fn register(&self, key: K, waker: &Waker) where K: PartialEq {
+        let mut waiting = self.waiting.lock();
+        if !waiting.iter().any(|(registered, existing)| *registered == key && existing.will_wake(waker)) {
+            waiting.push((key, waker.clone()));
+        }
+    }
+
+    /// Drops every waker stored under `key` without waking it
+    pub(crate) fn remove(&self, key: K) where K: PartialEq {
+        self.waiting.lock().retain(|(registered, _)| *registered != key);
+    }
+
+    /// Removes and wakes every waker whose key satisfies `is_ready`. The wakers are invoked after the lock is released
+    pub(crate) fn wake_where(&self, is_ready: impl Fn(K) -> bool) {
+        let mut ready = Vec::new();
+        {
+            let mut waiting = self.waiting.lock();
+            let mut idx = 0;
+            while idx < waiting.len() {
+                if is_ready(waiting[idx].0) {
+                    ready.push(waiting.swap_remove(idx).1);
+                } else {
+                    idx += 1;
+                }
+            }
+        }
+
+        for waker in ready {
+            waker.wake();
+        }
+    }
+}
+
+/// Whether a ticket belongs to a ReadVisitor or a WriteVisitor
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum TicketKind {
+    Read,
+    Write
+}
+
+/// A visitor's place in line. ReadVisitors sharing the same `writes_ahead` form one read epoch and are admitted together once
+/// every WriteVisitor ahead of them is released. A WriteVisitor is exclusive: it waits for every visitor issued before it
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Ticket {
+    pub(crate) kind: TicketKind,
+    /// The number of WriteVisitors issued before this ticket
+    pub(crate) writes_ahead: u32,
+    /// The number of ReadVisitors issued before this ticket
+    pub(crate) reads_ahead: u32
+}
+
+impl Ticket {
+    /// Whether the ticket may proceed, given how many write and read tickets have been released so far
+    #[inline]
+    pub(crate) fn is_admitted(&self, released_writes: usize, released_reads: usize) -> bool {
+        let writes_done = released_writes as u32 == self.writes_ahead;
+        match self.kind {
+            TicketKind::Read => writes_done,
+            TicketKind::Write => writes_done && released_reads as u32 == self.reads_ahead
+        }
+    }
+}
+
+/// Decides the order in which waiting visitors are admitted. Chosen when the HyperVec is constructed through
+/// [HyperVec::with_scheduling_policy]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SchedulingPolicy {
+    /// Visitors are admitted in the order they were issued, with back-to-back readers sharing an epoch. This is the default,
+    /// and the only policy which draws tickets without locking
+    Fifo,
+    /// Whenever the line drains, the longest-waiting writer is admitted ahead of every waiting reader
+    WriterPreferring,
+    /// Whenever the line drains, every waiting reader is admitted together ahead of any waiting writer
+    ReaderPreferring,
+    /// Visitors wait in the lane given to `cast_with_priority`/`cast_mut_with_priority`, clamped to `lanes - 1`. Whenever the
+    /// line drains, the highest non-empty lane is admitted first, keeping FIFO order within the lane
+    Priority {
+        /// The number of lanes, between 1 and [MAX_PRIORITY_LANES]
+        lanes: u8
+    }
+}
+
+/// The most lanes [SchedulingPolicy::Priority] may be configured with
+pub const MAX_PRIORITY_LANES: u8 = 8;
+
+impl Default for SchedulingPolicy {
+    fn default() -> Self {
+        SchedulingPolicy::Fifo
+    }
+}
+
+/// Where a visitor stands in line: holding a drawn ticket, or, under a non-FIFO policy, still queued for one
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Place {
+    Drawn(Ticket),
+    Queued(u64)
+}
+
+/// Everything a visitor needs to learn whether it may proceed: its place in line and, for a range-scoped visitor, the range it
+/// holds within its epoch
+pub(crate) struct Position {
+    pub(crate) place: Cell<Place>,
+    pub(crate) range: Option<RangeTicket>,
+    /// Whether the visitor was seen admitted. Its ticket is then released when the visitor finishes, rather than cancelled
+    pub(crate) admitted: Cell<bool>,
+    #[cfg(feature = "stats")]
+    pub(crate) timing: crate::stats::Timing
+}
+
+impl Position {
+    /// The position of a visitor over the whole buffer
+    pub(crate) fn whole(place: Place) -> Self {
+        Self { place: Cell::new(place), range: None, admitted: Cell::new(false), #[cfg(feature = "stats")] timing: crate::stats::Timing::start() }
+    }
+
+    /// The kind of visitor at this position. Must only be called once its ticket is drawn
+    #[cfg(feature = "stats")]
+    pub(crate) fn kind(&self) -> TicketKind {
+        match (&self.range, self.place.get()) {
+            (Some(range), _) => range.kind,
+            (None, Place::Drawn(ticket)) => ticket.kind,
+            (None, Place::Queued(_)) => unreachable!("the ticket has not been drawn")
+        }
+    }
+
+    /// The offset and length of the bytes the visitor may touch within a buffer of `len` bytes
+    #[inline]
+    pub(crate) fn region(&self, len: usize) -> (usize, usize) {
+        match &self.range {
+            Some(range) => (range.start, range.end - range.start),
+            None => (0, len)
+        }
+    }
+}
+
+/// A range-scoped visitor's membership in a range epoch
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct RangeTicket {
+    pub(crate) epoch: u64,
+    pub(crate) member: u64,
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+    /// Whether the visitor reads or writes, as opposed to the kind of the ticket its epoch holds
+    #[cfg(feature = "stats")]
+    pub(crate) kind: TicketKind
+}
+
+/// Range-scoped visitors issued back to back share one write ticket, and thus run only once every visitor before them is
+/// released. Within the epoch, a member waits only for the earlier members whose ranges it overlaps, unless both are readers
+pub(crate) struct RangeEpoch {
+    id: u64,
+    /// The place of the write ticket held on behalf of every member
+    place: Place,
+    /// The number of places taken in line when the epoch opened. Once another place is taken, the epoch closes to newcomers
+    entries: u64,
+    /// Whether any member saw the write ticket admitted, in which case it is released along with the last member
+    admitted: bool,
+    members: Vec<RangeMember>
+}
+
+/// A live member of a range epoch
+struct RangeMember {
+    id: u64,
+    start: usize,
+    end: usize,
+    exclusive: bool
+}
+
+impl RangeMember {
+    /// Whether this member and `other` may not run at once
+    fn conflicts(&self, other: &RangeMember) -> bool {
+        (self.exclusive || other.exclusive) && self.start < other.end && other.start < self.end
+    }
+}
+
+/// A visitor waiting for a ticket under a non-FIFO policy
+struct Request {
+    id: u64,
+    kind: TicketKind,
+    lane: u8
+}
+
+/// Issues tickets. The high half of `issued` counts WriteVisitors and the low half ReadVisitors, so that one atomic snapshot
+/// orders a new ticket against every ticket before it. Under FIFO, tickets are drawn the moment a visitor is issued; under any
+/// other policy, visitors queue in `state` and tickets are drawn for a batch of them whenever the line drains
+pub(crate) struct Scheduler {
+    issued: AtomicU64,
+    /// The number of places taken in line, including those still queued
+    pub(crate) entries: AtomicU64,
+    pub(crate) policy: SchedulingPolicy,
+    /// The number of cancelled tickets yet to be skipped, plus the releases currently holding `state`. While it is zero, a
+    /// FIFO release of an admitted ticket has nothing to settle and never takes the lock
+    pub(crate) unsettled: AtomicUsize,
+    /// Serializes releases against cancellations, so that a ticket is either released or skipped, never both
+    pub(crate) state: Mutex<SchedulerState>,
+    pub(crate) wakers: WakerRegistry<Place>
+}
+
+/// The parts of the scheduler which are only touched under its lock
+#[derive(Default)]
+pub(crate) struct SchedulerState {
+    pub(crate) cancelled: Cancellations,
+    queue: VecDeque<Request>,
+    /// Tickets drawn for queued visitors which have yet to pick them up
+    pub(crate) drawn: HashMap<u64, Ticket>,
+    next_request: u64,
+    /// Live range epochs, oldest first. Only the last may still be open
+    pub(crate) ranges: Vec<RangeEpoch>,
+    next_range: u64
+}
+
+impl SchedulerState {
+    /// Forgets a queued visitor which never had a ticket drawn
+    pub(crate) fn dequeue(&mut self, id: u64) {
+        self.queue.retain(|request| request.id != id);
+    }
+
+    /// Adds a member spanning [start, end) to the last range epoch, provided no place was taken in line since it opened
+    /// (`entries` being the current count)
+    pub(crate) fn join_range(&mut self, entries: u64, start: usize, end: usize, exclusive: bool) -> Option<Position> {
+        let member = self.next_range;
+        let epoch = self.ranges.last_mut().filter(|epoch| epoch.entries == entries)?;
+        epoch.members.push(RangeMember { id: member, start, end, exclusive });
+        let range = RangeTicket {
+            epoch: epoch.id,
+            member,
+            start,
+            end,
+            #[cfg(feature = "stats")]
+            kind: if exclusive { TicketKind::Write } else { TicketKind::Read }
+        };
+
+        let position = Position { place: Cell::new(epoch.place), range: Some(range), admitted: Cell::new(false), #[cfg(feature = "stats")] timing: crate::stats::Timing::start() };
+        self.next_range += 1;
+        Some(position)
+    }
+
+    /// Opens a range epoch holding the write ticket at `place` (the `entries`th place taken), whose first member spans [start, end)
+    pub(crate) fn open_range(&mut self, place: Place, entries: u64, start: usize, end: usize, exclusive: bool) -> Position {
+        let id = self.next_range;
+        self.next_range += 1;
+        self.ranges.push(RangeEpoch { id, place, entries, admitted: false, members: Vec::new() });
+        self.join_range(entries, start, end, exclusive).unwrap()
+    }
+
+    /// Notes that the epoch's write ticket was admitted, and returns whether no earlier member of the epoch holds a range
+    /// conflicting with `range`
+    pub(crate) fn admit_range(&mut self, range: &RangeTicket) -> bool {
+        let epoch = match self.ranges.iter_mut().find(|epoch| epoch.id == range.epoch) {
+            Some(epoch) => epoch,
+            None => return false
+        };
+
+        epoch.admitted = true;
+        let this = match epoch.members.iter().find(|member| member.id == range.member) {
+            Some(member) => member,
+            None => return false
+        };
+
+        !epoch.members.iter().take_while(|member| member.id != this.id).any(|member| member.conflicts(this))
+    }
+
+    /// Removes a member from its epoch, returning the epoch's place, whether it was the last member, and whether the epoch's
+    /// ticket was admitted. If it was the last member, the epoch is closed and its write ticket must be released
+    pub(crate) fn leave_range(&mut self, range: &RangeTicket) -> (Place, bool, bool) {
+        let idx = self.ranges.iter().position(|epoch| epoch.id == range.epoch).expect("a range epoch outlived its members");
+        let epoch = &mut self.ranges[idx];
+        epoch.members.retain(|member| member.id != range.member);
+        let (place, emptied, admitted) = (epoch.place, epoch.members.is_empty(), epoch.admitted);
+        if emptied {
+            let _ = self.ranges.remove(idx);
+        }
+
+        (place, emptied, admitted)
+    }
+}
+
+impl Scheduler {
+    /// Creates a scheduler which has not issued any tickets
+    pub(crate) fn new(policy: SchedulingPolicy) -> Self {
+        if let SchedulingPolicy::Priority { lanes } = policy {
+            assert!(lanes >= 1 && lanes <= MAX_PRIORITY_LANES, "between 1 and {} priority lanes are supported, but {} were requested", MAX_PRIORITY_LANES, lanes);
+        }
+
+        Self { issued: AtomicU64::new(0), entries: AtomicU64::new(0), policy, unsettled: AtomicUsize::new(0), state: Mutex::new(SchedulerState::default()), wakers: WakerRegistry::new() }
+    }
+
+    /// Draws the next ticket of the given kind
+    pub(crate) fn issue(&self, kind: TicketKind) -> Ticket {
+        let mut current = self.issued.load(Ordering::SeqCst);
+        loop {
+            let (writes, reads) = ((current >> 32) as u32, current as u32);
+            let next = match kind {
+                TicketKind::Read => pack(writes, reads.wrapping_add(1)),
+                TicketKind::Write => pack(writes.wrapping_add(1), reads)
+            };
+
+            match self.issued.compare_exchange_weak(current, next, Ordering::SeqCst, Ordering::SeqCst) {
+                Ok(_) => return Ticket { kind, writes_ahead: writes, reads_ahead: reads },
+                Err(actual) => current = actual
+            }
+        }
+    }
+
+    /// Queues a visitor under a non-FIFO policy, returning the id its ticket will be drawn under
+    pub(crate) fn enqueue(&self, state: &mut SchedulerState, kind: TicketKind, priority: u8) -> u64 {
+        let lane = match self.policy {
+            SchedulingPolicy::Priority { lanes } => std::cmp::min(priority, lanes - 1),
+            _ => 0
+        };
+
+        let id = state.next_request;
+        state.next_request += 1;
+        state.queue.push_back(Request { id, kind, lane });
+        id
+    }
+
+    /// Skips every cancelled ticket the line has reached, counting them off `unsettled`
+    pub(crate) fn settle(&self, state: &mut SchedulerState, released_writes: &AtomicUsize, released_reads: &AtomicUsize) {
+        let skipped = state.cancelled.settle(released_writes, released_reads);
+        if skipped != 0 {
+            let _ = self.unsettled.fetch_sub(skipped, Ordering::SeqCst);
+        }
+    }
+
+    /// Once every drawn ticket has been released, draws tickets for the next batch of queued visitors as the policy dictates.
+    /// Returns the ids of the visitors whose tickets were drawn
+    pub(crate) fn pump(&self, state: &mut SchedulerState, released_writes: usize, released_reads: usize) -> Vec<u64> {
+        let issued = self.issued.load(Ordering::SeqCst);
+        let idle = (issued >> 32) as u32 == released_writes as u32 && issued as u32 == released_reads as u32;
+        if !idle || state.queue.is_empty() {
+            return Vec::new();
+        }
+
+        let queue = &state.queue;
+        let readers = || (0..queue.len()).filter(|idx| queue[*idx].kind == TicketKind::Read).collect::<Vec<usize>>();
+        let batch = match self.policy {
+            SchedulingPolicy::WriterPreferring => match queue.iter().position(|request| request.kind == TicketKind::Write) {
+                Some(writer) => vec![writer],
+                None => readers()
+            },
+
+            SchedulingPolicy::ReaderPreferring => {
+                let readers = readers();
+                if readers.is_empty() { vec![0] } else { readers }
+            }
+
+            SchedulingPolicy::Priority { .. } => {
+                let lane = queue.iter().map(|request| request.lane).max().unwrap_or(0);
+                let members = (0..queue.len()).filter(|idx| queue[*idx].lane == lane).collect::<Vec<usize>>();
+                if queue[members[0]].kind == TicketKind::Write {
+                    vec![members[0]]
+                } else {
+                    members.into_iter().take_while(|idx| queue[*idx].kind == TicketKind::Read).collect()
+                }
+            }
+
+            SchedulingPolicy::Fifo => (0..queue.len()).collect()
+        };
+
+        let mut drawn = Vec::with_capacity(batch.len());
+        for idx in batch.into_iter().rev() {
+            let request = state.queue.remove(idx).unwrap();
+            drawn.push(request);
+        }
+
+        drawn.into_iter().rev().map(|request| {
+            let _ = state.drawn.insert(request.id, self.issue(request.kind));
+            request.id
+        }).collect()
+    }
+}
+
+/// Tickets dropped before they were admitted. They cannot be released on the spot without letting the tickets behind them
+/// run early, so they are recorded here and skipped once the line reaches them
+#[derive(Default)]
+pub(crate) struct Cancellations {
+    /// The number of ReadVisitors issued before each cancelled WriteVisitor, keyed by its serial number
+    writes: BTreeMap<u32, u32>,
+    /// The number of cancelled ReadVisitors per read epoch
+    reads: BTreeMap<u32, u32>
+}
+
+impl Cancellations {
+    /// Records that `ticket` will never be released
+    pub(crate) fn record(&mut self, ticket: Ticket) {
+        match ticket.kind {
+            TicketKind::Read => *self.reads.entry(ticket.writes_ahead).or_insert(0) += 1,
+            TicketKind::Write => {
+                let _ = self.writes.insert(ticket.writes_ahead, ticket.reads_ahead);
+            }
+        }
+    }
+
+    /// Skips every cancelled ticket the line has reached. A cancelled reader counts as released once its epoch begins, and a
+    /// cancelled writer once every visitor before it is released, as it would have been admitted then. Returns the number of
+    /// tickets skipped
+    pub(crate) fn settle(&mut self, released_writes: &AtomicUsize, released_reads: &AtomicUsize) -> usize {
+        let mut skipped = 0;
+        loop {
+            let epoch = released_writes.load(Ordering::SeqCst) as u32;
+            if let Some(count) = self.reads.remove(&epoch) {
+                let _ = released_reads.fetch_add(count as usize, Ordering::SeqCst);
+                skipped += count as usize;
+            }
+
+            match self.writes.get(&epoch) {
+                Some(reads_ahead) if *reads_ahead == released_reads.load(Ordering::SeqCst) as u32 => {
+                    let _ = self.writes.remove(&epoch);
+                    let _ = released_writes.fetch_add(1, Ordering::SeqCst);
+                    skipped += 1;
+                }
+
+                _ => return skipped
+            }
+        }
+    }
+}
+
+/// Resolves once a visitor is admitted, or with [MemError::TIMED_OUT] once its deadline passes
+pub(crate) struct AdmissionTimeout<'a> {
+    hvec: &'a HyperVec,
+    position: &'a Position,
+    deadline: Instant,
+    /// The waker last handed to the timer thread
+    armed: Option<Waker>
+}
+
+impl<'a> AdmissionTimeout<'a> {
+    /// Waits at most `timeout` for the visitor at `position` to be admitted
+    pub(crate) fn new(hvec: &'a HyperVec, position: &'a Position, timeout: Duration) -> Self {
+        Self { hvec, position, deadline: Instant::now() + timeout, armed: None }
+    }
+}
+
+impl<'a> Future for AdmissionTimeout<'a> {
+    type Output = InformationResult<'static, ()>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        if self.hvec.poll_admission(self.position, cx) {
+            return Poll::Ready(Ok(()));
+        }
+
+        if Instant::now() >= self.deadline {
+            return Poll::Ready(Err(MemError::TIMED_OUT));
+        }
+
+        let rearm = self.armed.as_ref().map(|armed| !armed.will_wake(cx.waker())).unwrap_or(true);
+        if rearm {
+            crate::util::timer::wake_at(self.deadline, cx.waker());
+            self.armed = Some(cx.waker().clone());
+        }
+
+        Poll::Pending
+    }
+}
+
+/// Stands in for a task on a thread which is parked rather than polled
+struct ThreadSignal {
+    woken: Mutex<bool>,
+    condvar: Condvar
+}
+
+impl ArcWake for ThreadSignal {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        *arc_self.woken.lock() = true;
+        let _ = arc_self.condvar.notify_one();
+    }
+}
+
+/// Parks the calling thread until the visitor at `position` is admitted. If `timeout` elapses first, returns [MemError::TIMED_OUT]
+pub(crate) fn wait_for_admission(hvec: &HyperVec, position: &Position, timeout: Option<Duration>) -> InformationResult<'static, ()> {
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    let signal = Arc::new(ThreadSignal { woken: Mutex::new(false), condvar: Condvar::new() });
+    let waker = signal.clone().into_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    loop {
+        if hvec.poll_admission(position, &mut cx) {
+            return Ok(());
+        }
+
+        let mut woken = signal.woken.lock();
+        while !*woken {
+            match deadline {
+                Some(deadline) => {
+                    if signal.condvar.wait_until(&mut woken, deadline).timed_out() {
+                        return if hvec.admits(position) { Ok(()) } else { Err(MemError::TIMED_OUT) };
+                    }
+                }
+
+                None => signal.condvar.wait(&mut woken)
+            }
+        }
+
+        *woken = false;
+    }
+}
+
+#[inline]
+fn pack(writes: u32, reads: u32) -> u64 {
+    (u64::from(writes) << 32) | u64::from(reads)
+}
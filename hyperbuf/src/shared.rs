@@ -0,0 +1,243 @@
+/*
+ * Copyright (c) 2019. The information/code/data contained within this file and all other files with the same copyright are protected under US Statutes. You must have explicit written access by Thomas P. Braun in order to access, view, modify, alter, or apply this code in any context commercial or non-commercial. If you have this code but were not given explicit written access by Thomas P. Braun, you must destroy the information herein for legal safety. You agree that if you apply the concepts herein without any written access, Thomas P. Braun will seek the maximum possible legal retribution.
+ */
+
+use std::cell::UnsafeCell;
+use std::fmt::{Debug, Formatter};
+use std::future::Future;
+use std::ops::{Deref, DerefMut, RangeBounds};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use crate::guard::{ReadGuard, WriteGuard};
+use crate::hypervec::HyperVec;
+#[cfg(feature = "stats")]
+use crate::stats::HyperVecStats;
+use crate::pod::HyperPod;
+use crate::results::InformationResult;
+use crate::scheduler::wait_for_admission;
+
+/// A clonable handle to a [HyperVec] which may be sent to and shared between threads. Every clone refers to the same buffer,
+/// and access is coordinated entirely through the buffer's tickets. The guards it issues own a clone of the handle, so they
+/// are `Send` and may be held across the awaits of a task spawned onto a multi-threaded runtime
+pub struct SharedHyperVec {
+    inner: Arc<UnsafeCell<HyperVec>>
+}
+
+// The tickets serialize every access to the inner HyperVec
+unsafe impl Send for SharedHyperVec {}
+unsafe impl Sync for SharedHyperVec {}
+
+impl SharedHyperVec {
+    /// Moves `hvec` behind a shared handle
+    pub fn new(hvec: HyperVec) -> Self {
+        Self { inner: Arc::new(UnsafeCell::new(hvec)) }
+    }
+
+    /// Draws a write ticket immediately, and resolves to a guard once every visitor ahead of it has been released
+    pub fn write<T: HyperPod>(&self) -> SharedWrite<T> {
+        self.write_with_priority(0)
+    }
+
+    /// Like [SharedHyperVec::write], but draws the ticket in the given lane. See [Castable::cast_mut_with_priority]
+    pub fn write_with_priority<T: HyperPod>(&self, priority: u8) -> SharedWrite<T> {
+        SharedWrite { pending: Some(self.issue_write::<T>(priority)) }
+    }
+
+    /// Draws a read ticket immediately, and resolves to a guard once every writer ahead of it has been released
+    pub fn read<T: HyperPod>(&self) -> SharedRead<T> {
+        self.read_with_priority(0)
+    }
+
+    /// Like [SharedHyperVec::read], but draws the ticket in the given lane. See [Castable::cast_with_priority]
+    pub fn read_with_priority<T: HyperPod>(&self, priority: u8) -> SharedRead<T> {
+        SharedRead { pending: Some(self.issue_read::<T>(priority)) }
+    }
+
+    /// Like [SharedHyperVec::read], but only locks the bytes within `range`, as [Castable::cast_range] does. Resolves
+    /// immediately to [MemError::OUT_OF_BOUNDS] if the range lies outside the buffer
+    pub fn cast_range<T: HyperPod, R: RangeBounds<usize>>(&self, range: R) -> SharedRead<T> {
+        let issued = unsafe { HyperVec::issue_range_read::<T, R>(self.inner.get(), range) };
+        SharedRead { pending: Some(issued.map(|visitor| SharedReadGuard { guard: ReadGuard { visitor }, handle: self.clone() })) }
+    }
+
+    /// Like [SharedHyperVec::write], but only locks the bytes within `range`, as [Castable::cast_range_mut] does, so that
+    /// tasks may write disjoint sections of the buffer at once
+    pub fn cast_range_mut<T: HyperPod, R: RangeBounds<usize>>(&self, range: R) -> SharedWrite<T> {
+        let issued = unsafe { HyperVec::issue_range_write::<T, R>(self.inner.get(), range) };
+        SharedWrite { pending: Some(issued.map(|visitor| SharedWriteGuard { guard: WriteGuard { visitor }, handle: self.clone() })) }
+    }
+
+    /// Parks the calling thread until a write ticket is admitted, then returns its guard
+    pub fn write_blocking<T: HyperPod>(&self) -> InformationResult<'static, SharedWriteGuard<T>> {
+        self.write_blocking_inner(None)
+    }
+
+    /// Like [SharedHyperVec::write_blocking], but gives up once `timeout` elapses, returning [MemError::TIMED_OUT]
+    pub fn write_blocking_timeout<T: HyperPod>(&self, timeout: Duration) -> InformationResult<'static, SharedWriteGuard<T>> {
+        self.write_blocking_inner(Some(timeout))
+    }
+
+    /// Parks the calling thread until a read ticket is admitted, then returns its guard
+    pub fn read_blocking<T: HyperPod>(&self) -> InformationResult<'static, SharedReadGuard<T>> {
+        self.read_blocking_inner(None)
+    }
+
+    /// Like [SharedHyperVec::read_blocking], but gives up once `timeout` elapses, returning [MemError::TIMED_OUT]
+    pub fn read_blocking_timeout<T: HyperPod>(&self, timeout: Duration) -> InformationResult<'static, SharedReadGuard<T>> {
+        self.read_blocking_inner(Some(timeout))
+    }
+
+    fn write_blocking_inner<T: HyperPod>(&self, timeout: Option<Duration>) -> InformationResult<'static, SharedWriteGuard<T>> {
+        let guard = self.issue_write::<T>(0)?;
+        wait_for_admission(self.get(), &guard.guard.visitor.position, timeout)?;
+        guard.guard.visitor.check_fit()?;
+        Ok(guard)
+    }
+
+    fn read_blocking_inner<T: HyperPod>(&self, timeout: Option<Duration>) -> InformationResult<'static, SharedReadGuard<T>> {
+        let guard = self.issue_read::<T>(0)?;
+        wait_for_admission(self.get(), &guard.guard.visitor.position, timeout)?;
+        guard.guard.visitor.check_fit()?;
+        Ok(guard)
+    }
+
+    // Visitors are issued through the cell's pointer, never through a `&mut HyperVec`, since other handles may be borrowing it
+    fn issue_write<T: HyperPod>(&self, priority: u8) -> InformationResult<'static, SharedWriteGuard<T>> {
+        unsafe { HyperVec::issue_write::<T>(self.inner.get(), priority) }
+            .map(|visitor| SharedWriteGuard { guard: WriteGuard { visitor }, handle: self.clone() })
+    }
+
+    fn issue_read<T: HyperPod>(&self, priority: u8) -> InformationResult<'static, SharedReadGuard<T>> {
+        unsafe { HyperVec::issue_read::<T>(self.inner.get(), priority) }
+            .map(|visitor| SharedReadGuard { guard: ReadGuard { visitor }, handle: self.clone() })
+    }
+
+    /// Copies the leading bytes out as a `T` without drawing a ticket, retrying whenever a writer tears the copy. See [HyperVec::read_consistent]
+    pub fn read_consistent<T: HyperPod>(&self) -> T {
+        self.get().read_consistent::<T>()
+    }
+
+    /// Returns a snapshot of the buffer's counters. See [HyperVec::stats]
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> HyperVecStats {
+        self.get().stats()
+    }
+
+    /// Returns the HyperVec if this is the only remaining handle, and no guards are outstanding
+    pub fn try_unwrap(self) -> Result<HyperVec, Self> {
+        Arc::try_unwrap(self.inner)
+            .map(UnsafeCell::into_inner)
+            .map_err(|inner| Self { inner })
+    }
+
+    fn get(&self) -> &HyperVec {
+        unsafe { &*self.inner.get() }
+    }
+}
+
+impl Clone for SharedHyperVec {
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone() }
+    }
+}
+
+impl Debug for SharedHyperVec {
+    // The bytes are left out, since reading them would race the writers admitted through other handles
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("SharedHyperVec").field("handles", &Arc::strong_count(&self.inner)).finish()
+    }
+}
+
+impl From<HyperVec> for SharedHyperVec {
+    fn from(hvec: HyperVec) -> Self {
+        Self::new(hvec)
+    }
+}
+
+/// Exclusive access to the bytes of a [SharedHyperVec] as a `T`. The write ticket is released when the guard drops
+pub struct SharedWriteGuard<T: 'static> {
+    guard: WriteGuard<'static, T>,
+    // Declared after the guard so that the ticket is released before the handle
+    handle: SharedHyperVec
+}
+
+unsafe impl<T: Send + 'static> Send for SharedWriteGuard<T> {}
+unsafe impl<T: Sync + 'static> Sync for SharedWriteGuard<T> {}
+
+/// Shared access to the bytes of a [SharedHyperVec] as a `T`. The read ticket is released when the guard drops
+pub struct SharedReadGuard<T: 'static> {
+    guard: ReadGuard<'static, T>,
+    // Declared after the guard so that the ticket is released before the handle
+    handle: SharedHyperVec
+}
+
+unsafe impl<T: Sync + 'static> Send for SharedReadGuard<T> {}
+unsafe impl<T: Sync + 'static> Sync for SharedReadGuard<T> {}
+
+impl<T: 'static> Deref for SharedWriteGuard<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.guard
+    }
+}
+
+impl<T: 'static> DerefMut for SharedWriteGuard<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.guard
+    }
+}
+
+impl<T: 'static> Deref for SharedReadGuard<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.guard
+    }
+}
+
+/// Resolves to a [SharedWriteGuard] once its ticket is admitted
+pub struct SharedWrite<T: 'static> {
+    pending: Option<InformationResult<'static, SharedWriteGuard<T>>>
+}
+
+/// Resolves to a [SharedReadGuard] once its ticket is admitted
+pub struct SharedRead<T: 'static> {
+    pending: Option<InformationResult<'static, SharedReadGuard<T>>>
+}
+
+impl<T: 'static> Future for SharedWrite<T> {
+    type Output = InformationResult<'static, SharedWriteGuard<T>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        if let Some(Ok(pending)) = &self.pending {
+            let visitor = &pending.guard.visitor;
+            if !pending.handle.get().poll_admission(&visitor.position, cx) {
+                return Poll::Pending;
+            }
+        }
+
+        // The size and alignment are checked again once admitted, since the writers ahead may have moved the buffer
+        let pending = self.pending.take().expect("SharedWrite polled after completion");
+        Poll::Ready(pending.and_then(|guard| guard.guard.visitor.check_fit().map(|_| guard)))
+    }
+}
+
+impl<T: 'static> Future for SharedRead<T> {
+    type Output = InformationResult<'static, SharedReadGuard<T>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        if let Some(Ok(pending)) = &self.pending {
+            let visitor = &pending.guard.visitor;
+            if !pending.handle.get().poll_admission(&visitor.position, cx) {
+                return Poll::Pending;
+            }
+        }
+
+        let pending = self.pending.take().expect("SharedRead polled after completion");
+        Poll::Ready(pending.and_then(|guard| guard.guard.visitor.check_fit().map(|_| guard)))
+    }
+}
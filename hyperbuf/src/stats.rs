@@ -0,0 +1,159 @@
+/*
+ * Copyright (c) 2019. The information/code/data contained within this file and all other files with the same copyright are protected under US Statutes. You must have explicit written access by Thomas P. Braun in order to access, view, modify, alter, or apply this code in any context commercial or non-commercial. If you have this code but were not given explicit written access by Thomas P. Braun, you must destroy the information herein for legal safety. You agree that if you apply the concepts herein without any written access, Thomas P. Braun will seek the maximum possible legal retribution.
+ */
+
+use std::cell::Cell;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::scheduler::TicketKind;
+
+/// A snapshot of the counters a HyperVec keeps, as returned by [HyperVec::stats]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct HyperVecStats {
+    /// The number of visitors issued
+    pub tickets_issued: u64,
+    /// The number of visitors dropped, whether or not they were admitted
+    pub tickets_completed: u64,
+    /// The number of visitors currently waiting to be admitted
+    pub queue_depth: u64,
+    /// How long ReadVisitors waited to be admitted
+    pub reads: WaitStats,
+    /// How long WriteVisitors waited to be admitted
+    pub writes: WaitStats,
+    /// The number of times a read was retried because a write occurred during it
+    pub out_of_sync_retries: u64,
+    /// The number of times a visitor reported writing more bytes than were available
+    pub corruption_events: u64,
+    /// The number of times the buffer moved into a larger or smaller allocation, such as when extended past its capacity
+    pub reallocations: u64
+}
+
+/// The time visitors of one kind spent waiting to be admitted
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct WaitStats {
+    /// The number of visitors admitted
+    pub admitted: u64,
+    /// The sum of every admitted visitor's wait
+    pub total_wait: Duration,
+    /// The longest any admitted visitor waited
+    pub max_wait: Duration
+}
+
+/// When a visitor took its place in line, and whether it has been admitted since
+pub(crate) struct Timing {
+    issued_at: Instant,
+    admitted: Cell<bool>
+}
+
+impl Timing {
+    /// Starts timing a visitor which was just issued
+    pub(crate) fn start() -> Self {
+        Self { issued_at: Instant::now(), admitted: Cell::new(false) }
+    }
+}
+
+/// The waits of one kind of visitor, in nanoseconds
+#[derive(Default)]
+struct WaitRecorder {
+    admitted: AtomicU64,
+    total: AtomicU64,
+    max: AtomicU64
+}
+
+impl WaitRecorder {
+    fn record(&self, wait: u64) {
+        let _ = self.admitted.fetch_add(1, Ordering::Relaxed);
+        let _ = self.total.fetch_add(wait, Ordering::Relaxed);
+        let mut max = self.max.load(Ordering::Relaxed);
+        while wait > max {
+            match self.max.compare_exchange_weak(max, wait, Ordering::Relaxed, Ordering::Relaxed) {
+                Ok(_) => break,
+                Err(actual) => max = actual
+            }
+        }
+    }
+
+    fn snapshot(&self) -> WaitStats {
+        WaitStats {
+            admitted: self.admitted.load(Ordering::Relaxed),
+            total_wait: Duration::from_nanos(self.total.load(Ordering::Relaxed)),
+            max_wait: Duration::from_nanos(self.max.load(Ordering::Relaxed))
+        }
+    }
+}
+
+/// The live counters behind [HyperVecStats]. Every update is a single relaxed atomic operation, so that collecting them costs
+/// next to nothing on the paths they instrument
+#[derive(Default)]
+pub(crate) struct StatsRecorder {
+    issued: AtomicU64,
+    completed: AtomicU64,
+    waiting: AtomicU64,
+    reads: WaitRecorder,
+    writes: WaitRecorder,
+    out_of_sync_retries: AtomicU64,
+    corruption_events: AtomicU64,
+    reallocations: AtomicU64
+}
+
+impl StatsRecorder {
+    /// Counts a newly issued visitor
+    pub(crate) fn issued(&self) {
+        let _ = self.issued.fetch_add(1, Ordering::Relaxed);
+        let _ = self.waiting.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records the wait of a visitor of the given kind, unless it was already recorded
+    pub(crate) fn admitted(&self, kind: TicketKind, timing: &Timing) {
+        if timing.admitted.replace(true) {
+            return;
+        }
+
+        let _ = self.waiting.fetch_sub(1, Ordering::Relaxed);
+        let wait = timing.issued_at.elapsed();
+        let wait = wait.as_secs().saturating_mul(1_000_000_000).saturating_add(u64::from(wait.subsec_nanos()));
+        match kind {
+            TicketKind::Read => self.reads.record(wait),
+            TicketKind::Write => self.writes.record(wait)
+        }
+    }
+
+    /// Counts a dropped visitor
+    pub(crate) fn completed(&self, timing: &Timing) {
+        if !timing.admitted.get() {
+            let _ = self.waiting.fetch_sub(1, Ordering::Relaxed);
+        }
+
+        let _ = self.completed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Counts a read retried for being out of sync
+    pub(crate) fn out_of_sync(&self) {
+        let _ = self.out_of_sync_retries.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Counts a visitor which marked the buffer corrupt
+    pub(crate) fn corrupted(&self) {
+        let _ = self.corruption_events.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Counts a reallocation of the buffer
+    pub(crate) fn reallocated(&self) {
+        let _ = self.reallocations.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Reads every counter
+    pub(crate) fn snapshot(&self) -> HyperVecStats {
+        HyperVecStats {
+            tickets_issued: self.issued.load(Ordering::Relaxed),
+            tickets_completed: self.completed.load(Ordering::Relaxed),
+            queue_depth: self.waiting.load(Ordering::Relaxed),
+            reads: self.reads.snapshot(),
+            writes: self.writes.snapshot(),
+            out_of_sync_retries: self.out_of_sync_retries.load(Ordering::Relaxed),
+            corruption_events: self.corruption_events.load(Ordering::Relaxed),
+            reallocations: self.reallocations.load(Ordering::Relaxed)
+        }
+    }
+}
@@ -0,0 +1,60 @@
+/*
+ * Copyright (c) 2019. The information/code/data contained within this file and all other files with the same copyright are protected under US Statutes. You must have explicit written access by Thomas P. Braun in order to access, view, modify, alter, or apply this code in any context commercial or non-commercial. If you have this code but were not given explicit written access by Thomas P. Braun, you must destroy the information herein for legal safety. You agree that if you apply the concepts herein without any written access, Thomas P. Braun will seek the maximum possible legal retribution.
+ */
+
+use std::fmt::{Debug, Formatter};
+
+use crate::pod::HyperPod;
+
+/// Holds a `T` with an alignment of 1, so that it may be cast from (or placed within a struct cast from) any offset of a
+/// HyperVec. Wire formats rarely align their fields, and the bytes of a HyperVec are only guaranteed an alignment of 1, so a
+/// `u64` field is best declared as `Unaligned<u64>`. The value is only ever copied in and out, never borrowed
+#[repr(C, packed)]
+#[derive(Copy)]
+pub struct Unaligned<T: HyperPod>(T);
+
+// Deriving Clone would borrow the packed field
+impl<T: HyperPod> Clone for Unaligned<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+// A packed HyperPod has neither padding nor invalid bit patterns
+unsafe impl<T: HyperPod> HyperPod for Unaligned<T> {}
+
+impl<T: HyperPod> Unaligned<T> {
+    /// Wraps `value`
+    pub fn new(value: T) -> Self {
+        Unaligned(value)
+    }
+
+    /// Copies the value out
+    #[inline]
+    pub fn get(&self) -> T {
+        unsafe { std::ptr::read_unaligned(self as *const Self as *const T) }
+    }
+
+    /// Replaces the value
+    #[inline]
+    pub fn set(&mut self, value: T) {
+        unsafe { std::ptr::write_unaligned(self as *mut Self as *mut T, value) }
+    }
+
+    /// Unwraps the value
+    pub fn into_inner(self) -> T {
+        self.get()
+    }
+}
+
+impl<T: HyperPod> From<T> for Unaligned<T> {
+    fn from(value: T) -> Self {
+        Unaligned::new(value)
+    }
+}
+
+impl<T: HyperPod + Debug> Debug for Unaligned<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        write!(f, "Unaligned({:?})", self.get())
+    }
+}
@@ -34,6 +34,141 @@ pub mod bit_handler {
     }
 }
 
+/// Translates `range` into a pair of (start, end) indices within a buffer of length `len`. Panics if the range lies out of bounds
+pub(crate) fn resolve_range<R: std::ops::RangeBounds<usize>>(range: &R, len: usize) -> (usize, usize) {
+    let (start, end) = range_bounds(range, len);
+    assert!(start <= end, "range start {} is greater than the range end {}", start, end);
+    assert!(end <= len, "range end {} out of bounds for a buffer of length {}", end, len);
+    (start, end)
+}
+
+/// Like [resolve_range], but rather than panicking if the range lies out of bounds, returns its unchecked indices as the error
+pub(crate) fn checked_range<R: std::ops::RangeBounds<usize>>(range: &R, len: usize) -> Result<(usize, usize), (usize, usize)> {
+    let (start, end) = range_bounds(range, len);
+    if start <= end && end <= len {
+        Ok((start, end))
+    } else {
+        Err((start, end))
+    }
+}
+
+/// Translates `range` into a pair of (start, end) indices without checking them
+fn range_bounds<R: std::ops::RangeBounds<usize>>(range: &R, len: usize) -> (usize, usize) {
+    use std::ops::Bound;
+
+    let start = match range.start_bound() {
+        Bound::Included(start) => *start,
+        Bound::Excluded(start) => start.checked_add(1).expect("range start overflowed"),
+        Bound::Unbounded => 0
+    };
+
+    let end = match range.end_bound() {
+        Bound::Included(end) => end.checked_add(1).expect("range end overflowed"),
+        Bound::Excluded(end) => *end,
+        Bound::Unbounded => len
+    };
+
+    (start, end)
+}
+
+/// A single background thread which wakes tasks at their deadlines, so that timeouts work under any executor
+pub(crate) mod timer {
+    use std::cmp::Ordering;
+    use std::collections::BinaryHeap;
+    use std::sync::Once;
+    use std::task::Waker;
+    use std::time::Instant;
+
+    use parking_lot::{Condvar, Mutex};
+
+    struct Entry {
+        deadline: Instant,
+        waker: Waker
+    }
+
+    // Reversed, so that the BinaryHeap pops the earliest deadline first
+    impl Ord for Entry {
+        fn cmp(&self, other: &Self) -> Ordering {
+            other.deadline.cmp(&self.deadline)
+        }
+    }
+
+    impl PartialOrd for Entry {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl PartialEq for Entry {
+        fn eq(&self, other: &Self) -> bool {
+            self.deadline == other.deadline
+        }
+    }
+
+    impl Eq for Entry {}
+
+    struct Timer {
+        queue: Mutex<BinaryHeap<Entry>>,
+        condvar: Condvar
+    }
+
+    static INIT: Once = Once::new();
+    static mut TIMER: *const Timer = std::ptr::null();
+
+    fn timer() -> &'static Timer {
+        unsafe {
+            INIT.call_once(|| {
+                TIMER = Box::into_raw(Box::new(Timer { queue: Mutex::new(BinaryHeap::new()), condvar: Condvar::new() }));
+                let _ = std::thread::Builder::new()
+                    .name("hyperbuf-timer".to_string())
+                    .spawn(|| run(&*TIMER))
+                    .expect("unable to spawn the timer thread");
+            });
+
+            &*TIMER
+        }
+    }
+
+    /// Wakes `waker` once `deadline` has passed
+    pub(crate) fn wake_at(deadline: Instant, waker: &Waker) {
+        let timer = timer();
+        let mut queue = timer.queue.lock();
+        let earliest = queue.peek().map(|entry| deadline < entry.deadline).unwrap_or(true);
+        queue.push(Entry { deadline, waker: waker.clone() });
+        if earliest {
+            let _ = timer.condvar.notify_one();
+        }
+    }
+
+    fn run(timer: &Timer) {
+        let mut queue = timer.queue.lock();
+        loop {
+            let now = Instant::now();
+            let mut expired = Vec::new();
+            while queue.peek().map(|entry| entry.deadline <= now).unwrap_or(false) {
+                expired.push(queue.pop().unwrap().waker);
+            }
+
+            if !expired.is_empty() {
+                drop(queue);
+                for waker in expired {
+                    waker.wake();
+                }
+                queue = timer.queue.lock();
+                continue;
+            }
+
+            match queue.peek().map(|entry| entry.deadline) {
+                Some(deadline) => {
+                    let _ = timer.condvar.wait_until(&mut queue, deadline);
+                }
+
+                None => timer.condvar.wait(&mut queue)
+            }
+        }
+    }
+}
+
 pub(super) mod ser {
     use std::fs::File;
     //use tokio::fs::File;
@@ -88,7 +223,6 @@ pub(super) mod ser {
     #[allow(unused)]
     pub fn ptr_serialize<T: Sized>(t: &T) -> Box<[u8]> {
         let size = std::mem::size_of_val(&t);
-        println!("Will serialize {} bytes", size);
         let mut bytes = Vec::<u8>::with_capacity(size);
         let ptr = t as *const T;
         let ptr = ptr as *const u8;
@@ -0,0 +1,396 @@
+/*
+ * Copyright (c) 2019. The information/code/data contained within this file and all other files with the same copyright are protected under US Statutes. You must have explicit written access by Thomas P. Braun in order to access, view, modify, alter, or apply this code in any context commercial or non-commercial. If you have this code but were not given explicit written access by Thomas P. Braun, you must destroy the information herein for legal safety. You agree that if you apply the concepts herein without any written access, Thomas P. Braun will seek the maximum possible legal retribution.
+ */
+
+use hyperbuf::hypervec::{Endianness, HyperVec};
+use hyperbuf::prelude::{BytePusher, ByteReader, Castable, MemError, SharedHyperVec, Unaligned};
+use hyperbuf::hyperbytes::HyperBytes;
+use bytes::Buf;
+use futures::executor::block_on;
+
+#[test]
+fn capacity_growth() {
+    let mut hvec = HyperVec::with_capacity(4);
+    assert_eq!(hvec.length(), 0);
+    assert_eq!(hvec.capacity(), 4);
+
+    let reserved = HyperVec::new(16);
+    assert_eq!((reserved.length(), reserved.capacity()), (0, 16));
+
+    hvec.push_u8s(&[1, 2, 3]);
+    assert_eq!(hvec.capacity(), 4);
+    hvec.push_u8s(&[4, 5]);
+    assert_eq!(hvec.length(), 5);
+    assert_eq!(hvec.capacity(), 8);
+
+    for byte in 0..100u8 {
+        hvec.push_u8s(&[byte]);
+    }
+    assert_eq!(hvec.length(), 105);
+    assert!(hvec.capacity() >= 105);
+    assert_eq!(unsafe { &hvec.get_full_bytes()[..5] }, &[1, 2, 3, 4, 5]);
+
+    hvec.shrink_to_fit();
+    assert_eq!(hvec.capacity(), 105);
+
+    hvec.reserve_exact(3);
+    assert_eq!(hvec.capacity(), 108);
+}
+
+#[test]
+fn bounds_checked_access() {
+    let mut hvec = HyperVec::with_capacity(16);
+    hvec.push_u8s(&[10, 20, 30, 40]);
+
+    assert_eq!(hvec.get(0), Some(&10));
+    assert_eq!(hvec.get(4), None);
+    assert_eq!(hvec.get(-1), None);
+    assert_eq!(hvec.get_range(1..3), Some(&[20u8, 30][..]));
+    assert_eq!(hvec.get_range(2..5), None);
+
+    *hvec.get_mut(3).unwrap() = 41;
+    assert_eq!(&hvec[..], &[10, 20, 30, 41]);
+    assert_eq!(&hvec[2..], &[30, 41]);
+    assert_eq!(&hvec[..=1], &[10, 20]);
+    hvec[0..2].copy_from_slice(&[11, 21]);
+    assert_eq!(hvec[1], 21);
+}
+
+#[test]
+#[should_panic]
+fn index_past_length_panics() {
+    let hvec = HyperVec::with_capacity(16);
+    let _ = hvec[0];
+}
+
+#[test]
+fn safe_slice_access() {
+    let mut hvec = HyperVec::with_capacity(4);
+    hvec.push_u8s(&[1, 2, 3]);
+
+    assert_eq!(hvec.as_slice(), &[1, 2, 3]);
+    assert_eq!(hvec.len(), 3);
+    assert!(hvec.starts_with(&[1, 2]));
+
+    hvec.as_mut_slice()[0] = 9;
+    hvec.reverse();
+    assert_eq!(AsRef::<[u8]>::as_ref(&hvec), &[3, 2, 9]);
+
+    let borrowed: &[u8] = std::borrow::Borrow::borrow(&hvec);
+    assert_eq!(borrowed, &[3, 2, 9]);
+
+    let reserved = HyperVec::new(8);
+    assert!(reserved.is_empty());
+    assert!(format!("{:?}", reserved).contains("bytes: []"));
+}
+
+#[test]
+fn split_and_freeze() {
+    let mut hvec = HyperVec::with_capacity(8);
+    hvec.push_u8s(&[1, 2, 3, 4, 5, 6]);
+
+    let head = hvec.split_to(2);
+    assert_eq!(head.as_slice(), &[1, 2]);
+    assert_eq!(hvec.as_slice(), &[3, 4, 5, 6]);
+    assert_eq!(head.as_ptr(), unsafe { hvec.as_ptr().offset(-2) });
+
+    let tail = hvec.split_off(3);
+    assert_eq!(hvec.as_slice(), &[3, 4, 5]);
+    assert_eq!(tail.as_slice(), &[6]);
+
+    // growing a view moves it into an allocation of its own, leaving the other views untouched
+    hvec.extend(10);
+    assert_eq!(&hvec[..3], &[3, 4, 5]);
+    assert_eq!(tail.as_slice(), &[6]);
+
+    let frozen: HyperBytes = head.freeze();
+    let shared = frozen.clone();
+    let handle = std::thread::spawn(move || shared.iter().map(|byte| u32::from(*byte)).sum::<u32>());
+    assert_eq!(handle.join().unwrap(), 3);
+    assert_eq!(frozen.slice(1..).as_ref(), &[2]);
+}
+
+#[test]
+fn editing_operations() {
+    let mut hvec = HyperVec::with_capacity(8);
+    hvec.push_u8s(&[0, 1, 2, 3, 4, 5, 6, 7]);
+    hvec.set_cursor_pos(6);
+
+    hvec.insert_slice(2, &[100, 101]);
+    assert_eq!(hvec.as_slice(), &[0, 1, 100, 101, 2, 3, 4, 5, 6, 7]);
+    assert_eq!(hvec.cursor_position(), 8);
+    assert_eq!(hvec.read_cursor(), 6);
+
+    let drained: Vec<u8> = hvec.drain(..4).collect();
+    assert_eq!(drained, vec![0, 1, 100, 101]);
+    assert_eq!(hvec.read_cursor(), 6);
+
+    let replaced: Vec<u8> = hvec.splice(1..3, &[9]).collect();
+    assert_eq!(replaced, vec![3, 4]);
+    assert_eq!(hvec.as_slice(), &[2, 9, 5, 6, 7]);
+    assert_eq!(hvec.read_cursor(), 6);
+
+    hvec.remove_range(3..);
+    assert_eq!(hvec.as_slice(), &[2, 9, 5]);
+    assert_eq!(hvec.cursor_position(), 3);
+
+    hvec.extend_from_within(..2);
+    hvec.copy_within(0..1, 4);
+    assert_eq!(hvec.as_slice(), &[2, 9, 5, 2, 2]);
+
+    hvec.resize(7, 0xFF);
+    assert_eq!(&hvec[5..], &[0xFF, 0xFF]);
+    hvec.truncate(1);
+    assert_eq!(hvec.as_slice(), &[2]);
+    assert_eq!(hvec.cursor_position(), 1);
+
+    hvec.clear();
+    assert!(hvec.is_empty());
+    assert_eq!(hvec.cursor_position(), 0);
+}
+
+#[test]
+fn conversions_and_comparisons() {
+    let vec = vec![1u8, 2, 3];
+    let vec_ptr = vec.as_ptr();
+    let hvec = HyperVec::from(vec);
+    assert_eq!(hvec.as_ptr(), vec_ptr);
+
+    let back: Vec<u8> = hvec.clone().into();
+    assert_eq!(back, vec![1, 2, 3]);
+    let back: Vec<u8> = hvec.into();
+    assert_eq!(back.as_ptr(), vec_ptr);
+
+    let mut collected: HyperVec = (0..4u8).collect();
+    Extend::extend(&mut collected, vec![4u8, 5]);
+    Extend::extend(&mut collected, &[6u8]);
+    assert_eq!(collected, vec![0, 1, 2, 3, 4, 5, 6]);
+    assert_eq!(collected, HyperVec::from(&[0u8, 1, 2, 3, 4, 5, 6][..]));
+    assert!(HyperVec::from(vec![1u8, 2]) < HyperVec::from(vec![1u8, 3]));
+
+    let mut map = std::collections::HashMap::new();
+    let _ = map.insert(HyperVec::from(vec![9u8, 9]), "nines");
+    assert_eq!(map.get(&[9u8, 9][..]), Some(&"nines"));
+    assert!(format!("{:?}", collected).contains("[0, 1, 2, 3, 4, 5, 6]"));
+}
+
+#[test]
+fn borrowing_and_typed_iterators() {
+    let mut hvec = HyperVec::from(vec![0u8, 1, 0, 2, 0, 3, 9]);
+    hvec.advance_cursor_by(2);
+
+    // With Buf in scope, method syntax picks its by-value Buf::iter over the borrowing one
+    assert_eq!(HyperVec::iter(&hvec).count(), 7);
+    for byte in hvec.iter_mut() {
+        *byte = byte.wrapping_add(1);
+    }
+    assert_eq!(hvec.cursor_position(), 2);
+    assert_eq!((&hvec).into_iter().cloned().collect::<Vec<u8>>(), vec![1, 2, 1, 3, 1, 4, 10]);
+
+    assert_eq!(hvec.chunks(3).map(|chunk| chunk.len()).collect::<Vec<usize>>(), vec![3, 3, 1]);
+    assert_eq!(hvec.chunks_exact(3).count(), 2);
+
+    unsafe { hvec.set_endianness(Endianness::BE) };
+    assert_eq!(hvec.iter_as::<u16>().collect::<Vec<u16>>(), vec![0x0102, 0x0103, 0x0104]);
+    unsafe { hvec.set_endianness(Endianness::LE) };
+    assert_eq!(hvec.iter_as::<u16>().rev().next(), Some(0x0401));
+
+    // Iterating the HyperVec itself reads from the cursor onwards, advancing it
+    assert_eq!(hvec.next(), Some(1));
+    assert_eq!(hvec.cursor_position(), 3);
+    let mut rest = Vec::new();
+    rest.extend(hvec);
+    assert_eq!(rest, vec![3, 1, 4, 10]);
+}
+
+#[test]
+fn buf_reads_follow_cursor() {
+    fn decode_header<B: Buf>(buf: &mut B) -> (u8, u16) {
+        (buf.get_u8(), buf.get_u16_be())
+    }
+
+    let mut hvec = HyperVec::from(vec![7u8, 0x01, 0x02, 0x03, 0x04, 0xAA, 0xBB, 0xCC]);
+    assert_eq!(decode_header(&mut hvec), (7, 0x0102));
+    assert_eq!(hvec.cursor_position(), 3);
+    assert_eq!(hvec.remaining(), 5);
+
+    // The _be/_le readers keep their byte order, whereas the unsuffixed ones follow the buffer's endianness
+    unsafe { hvec.set_endianness(Endianness::LE) };
+    assert_eq!(hvec.peek_u16().unwrap(), 0x0403);
+    assert_eq!(hvec.get_u16(), 0x0403);
+    hvec.set_cursor_pos(3);
+    assert_eq!(hvec.get_u16_be(), 0x0304);
+
+    let mut tail = [0u8; 3];
+    hvec.copy_to_slice(&mut tail);
+    assert_eq!(tail, [0xAA, 0xBB, 0xCC]);
+    assert!(!hvec.has_remaining());
+}
+
+#[test]
+fn typed_cursor_reads() {
+    let mut hvec = HyperVec::with_capacity(16);
+    unsafe { hvec.set_endianness(Endianness::BE) };
+    hvec.push_u16s([0xBEEF]);
+    hvec.push_i32s([-2]);
+    hvec.reset_cursor();
+
+    assert_eq!(hvec.peek_u16().unwrap(), 0xBEEF);
+    assert_eq!(hvec.read_u16().unwrap(), 0xBEEF);
+    assert_eq!(hvec.read_i32().unwrap(), -2);
+    assert_eq!(hvec.read_u8_at(1).unwrap(), 0xEF);
+    assert_eq!(hvec.read_u32_at(2).unwrap(), 0xFFFF_FFFE);
+
+    match hvec.read_u16() {
+        Err(MemError::OUT_OF_BOUNDS { offset, requested, len }) => assert_eq!((offset, requested, len), (6, 2, 6)),
+        _ => panic!("reading past the end must fail")
+    }
+    assert_eq!(hvec.cursor_position(), 6);
+    assert!(hvec.read_u128_at(usize::max_value()).is_err());
+}
+
+#[test]
+fn put_slice_past_the_length_zeroes_the_gap() {
+    use bytes::BufMut;
+
+    let mut hvec = HyperVec::from(vec![9u8; 2]);
+    hvec.set_cursor_pos(4);
+    hvec.put_slice(&[1, 2]);
+    assert_eq!(hvec.as_slice(), &[9, 9, 0, 0, 1, 2]);
+    assert_eq!(hvec.cursor_position(), 6);
+}
+
+#[test]
+fn std_io_traits() {
+    use std::io::{BufRead, Read, Seek, SeekFrom, Write};
+
+    let mut hvec = HyperVec::with_capacity(4);
+    hvec.write_all(b"first\nsecond\n").unwrap();
+    assert_eq!(hvec.length(), 13);
+
+    assert_eq!(hvec.seek(SeekFrom::Start(0)).unwrap(), 0);
+    let lines = (&mut hvec).lines().map(|line| line.unwrap()).collect::<Vec<String>>();
+    assert_eq!(lines, vec!["first", "second"]);
+
+    assert_eq!(hvec.seek(SeekFrom::End(-7)).unwrap(), 6);
+    let mut out = Vec::new();
+    assert_eq!(std::io::copy(&mut hvec, &mut out).unwrap(), 7);
+    assert_eq!(out, b"second\n");
+
+    assert_eq!(hvec.seek(SeekFrom::Current(-13)).unwrap(), 0);
+    let mut word = [0u8; 5];
+    hvec.read_exact(&mut word).unwrap();
+    assert_eq!(&word, b"first");
+
+    assert!(hvec.seek(SeekFrom::Current(-6)).is_err());
+    assert!(hvec.seek(SeekFrom::End(1)).is_err());
+    assert_eq!(hvec.cursor_position(), 5);
+
+    let mut rest = [0u8; 8];
+    assert_eq!(hvec.read(&mut rest).unwrap(), 8);
+    assert_eq!(&rest, b"\nsecond\n");
+    assert_eq!(hvec.write(b"third").unwrap(), 5);
+    assert_eq!(hvec.length(), 18);
+}
+
+#[test]
+fn casts_reject_short_and_misaligned_bytes() {
+    let mut hvec = HyperVec::new_zeroed(2);
+    match hvec.cast::<u32>() {
+        Err(MemError::BAD_SIZE { expected, actual }) => assert_eq!((expected, actual), (4, 2)),
+        _ => panic!("a u32 cannot be cast from 2 bytes")
+    }
+
+    hvec.extend(6);
+    assert!(hvec.cast_mut::<u32>().is_ok());
+    match hvec.cast_range::<u32, _>(1..5) {
+        Err(MemError::BAD_ALIGN { expected, actual }) => assert_eq!((expected, actual), (4, 1)),
+        _ => panic!("a u32 cannot be cast from an odd address")
+    };
+}
+
+#[test]
+fn pre_allocation_keeps_the_bytes_aligned() {
+    let mut hvec = HyperVec::new_zeroed(8);
+    hvec.cast_mut::<u64>().unwrap().visit_blocking(Some(8), |visitor| {
+        let value = visitor.write().unwrap();
+        assert_eq!(value as *mut u64 as usize % std::mem::align_of::<u64>(), 0);
+        *value = 7;
+        Some(8)
+    }).unwrap();
+    assert_eq!(hvec.length(), 16);
+    assert_eq!(hvec.read_unaligned::<u64>(0).unwrap(), 7);
+}
+
+#[test]
+fn casts_are_checked_when_issued_and_again_once_admitted() {
+    let mut short = HyperVec::new_zeroed(2);
+    match short.cast::<u32>() {
+        Err(MemError::BAD_SIZE { expected, actual }) => assert_eq!((expected, actual), (4, 2)),
+        _ => panic!("a u32 cannot be read from two bytes")
+    };
+    match short.cast_mut::<u32>() {
+        Err(MemError::BAD_SIZE { expected, actual }) => assert_eq!((expected, actual), (4, 2)),
+        _ => panic!("a u32 cannot be written into two bytes")
+    };
+
+    // Nothing safe shrinks the buffer while visitors are outstanding, so a raw pointer stands in for a writer ahead which does
+    let mut hvec = HyperVec::new_zeroed(8);
+    let hvec_ptr = &mut hvec as *mut HyperVec;
+    let reader = unsafe { (*hvec_ptr).cast::<u64>() }.unwrap();
+    let writer = unsafe { (*hvec_ptr).cast_mut::<u64>() }.unwrap();
+    unsafe { (*hvec_ptr).truncate(4) };
+
+    assert!(reader.read().is_none());
+    match reader.visit_blocking(|_| {}) {
+        Err(MemError::BAD_SIZE { expected, actual }) => assert_eq!((expected, actual), (8, 4)),
+        _ => panic!("the reader must find the truncated buffer too short once admitted")
+    }
+
+    assert!(writer.write().is_none());
+    match writer.visit_blocking(None, |_| None) {
+        Err(MemError::BAD_SIZE { expected, actual }) => assert_eq!((expected, actual), (8, 4)),
+        _ => panic!("the writer must find the truncated buffer too short once admitted")
+    }
+}
+
+#[test]
+fn header_and_payload_are_cast_in_place() {
+    let shared = SharedHyperVec::new(HyperVec::new_zeroed(16));
+    {
+        let mut header = block_on(shared.cast_range_mut::<u32, _>(0..4)).unwrap();
+        let mut payload = block_on(shared.cast_range_mut::<[u32; 3], _>(4..16)).unwrap();
+        *header = 3;
+        *payload = [1, 2, 3];
+    }
+
+    let hvec = shared.try_unwrap().unwrap();
+    let count = *hvec.cast_at::<u32>(0).unwrap().read().unwrap();
+    let payload = hvec.cast_slice_at::<u32>(4, count as usize).unwrap();
+    assert_eq!(payload.read_array().unwrap(), &[1, 2, 3]);
+
+    match hvec.cast_slice_at::<u32>(4, 4) {
+        Err(MemError::OUT_OF_BOUNDS { offset, requested, len }) => assert_eq!((offset, requested, len), (4, 16, 16)),
+        _ => panic!("the payload cannot extend past the buffer")
+    }
+
+    match hvec.cast_at::<u32>(2) {
+        Err(MemError::BAD_ALIGN { expected, .. }) => assert_eq!(expected, 4),
+        _ => panic!("a u32 cannot be cast from a misaligned offset")
+    };
+}
+
+#[test]
+fn unaligned_values_round_trip_at_any_offset() {
+    let mut hvec = HyperVec::new_zeroed(11);
+    hvec.write_unaligned::<u64>(3, 0x0102_0304_0506_0708).unwrap();
+    assert_eq!(hvec.read_unaligned::<u64>(3).unwrap(), 0x0102_0304_0506_0708);
+    assert!(hvec.read_unaligned::<u64>(4).is_err());
+
+    {
+        let view = hvec.cast_mut_at::<Unaligned<u64>>(3).unwrap();
+        view.write().unwrap().set(9);
+        assert_eq!(view.write().unwrap().get(), 9);
+    }
+    assert_eq!(hvec.read_unaligned::<u64>(3).unwrap(), 9);
+}
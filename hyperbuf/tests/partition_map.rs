@@ -18,4 +18,18 @@ mod tests {
 
         println!("{}", pm);
     }
+
+    #[test]
+    fn test_partition_map_adjust_for_edit() {
+        let mut pm = PartitionMap::new();
+        pm.store(0, 4, u32::type_id(&0));
+        pm.store(4, 8, u64::type_id(&0));
+        pm.store(12, 2, u16::type_id(&0));
+
+        // removing bytes 6..8 splits the u64, so only the u32 and the (shifted) u16 remain
+        pm.adjust_for_edit(6, 2, 0);
+        let remaining = format!("{}", pm);
+        assert!(remaining.contains("[length: 2]"));
+        assert!(remaining.contains("[relative location: 10] [size: 2]"));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,106 @@
+/*
+ * Copyright (c) 2019. The information/code/data contained within this file and all other files with the same copyright are protected under US Statutes. You must have explicit written access by Thomas P. Braun in order to access, view, modify, alter, or apply this code in any context commercial or non-commercial. If you have this code but were not given explicit written access by Thomas P. Braun, you must destroy the information herein for legal safety. You agree that if you apply the concepts herein without any written access, Thomas P. Braun will seek the maximum possible legal retribution.
+ */
+
+use hyperbuf::hypervec::HyperVec;
+use hyperbuf::prelude::{Castable, F32Le, F64Be, HyperPod, I16Le, I32Be, U16Be, U16Le, U32Le};
+
+#[repr(C)]
+#[derive(Copy, Clone, HyperPod)]
+struct Header {
+    kind: u16,
+    flags: u16,
+    length: u32,
+    checksum: [u8; 8]
+}
+
+#[test]
+fn derived_pods_can_be_cast() {
+    let mut hvec = HyperVec::new_zeroed(std::mem::size_of::<Header>());
+    {
+        let writer = hvec.cast_mut::<Header>().unwrap();
+        let header = writer.write().unwrap();
+        header.kind = 7;
+        header.length = 64;
+        header.checksum[7] = 0xFF;
+    }
+
+    let reader = hvec.cast::<Header>().unwrap();
+    let header = reader.read().unwrap();
+    assert_eq!((header.kind, header.flags, header.length, header.checksum[7]), (7, 0, 64, 0xFF));
+}
+
+mod renamed {
+    use hyperbuf as wire;
+    use hyperbuf::prelude::HyperPod;
+
+    /// Padded by hand, since the derive rejects implicit padding
+    #[repr(C)]
+    #[derive(Copy, Clone, HyperPod)]
+    #[hyperpod(crate = "wire")]
+    pub struct Record {
+        pub tag: u8,
+        pub _pad: [u8; 3],
+        pub value: u32
+    }
+}
+
+#[test]
+fn derive_accepts_a_crate_override() {
+    let record = renamed::Record { tag: 1, _pad: [0; 3], value: 0xAABB };
+    let hvec = HyperVec::wrap(&record);
+    let reader = hvec.cast::<renamed::Record>().unwrap();
+    let record = reader.read().unwrap();
+    assert_eq!((record.tag, record.value), (1, 0xAABB));
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, HyperPod)]
+struct WireHeader {
+    kind: U16Be,
+    length: U32Le
+}
+
+#[test]
+fn endian_fields_are_cast_at_any_offset() {
+    let mut hvec = HyperVec::new_zeroed(7);
+    {
+        let writer = hvec.cast_mut_at::<WireHeader>(1).unwrap();
+        let header = writer.write().unwrap();
+        header.kind.set(0x0102);
+        header.length = U32Le::new(0x0304_0506).wrapping_add(1);
+    }
+
+    assert_eq!(hvec.as_slice(), &[0, 0x01, 0x02, 0x07, 0x05, 0x04, 0x03]);
+    let reader = hvec.cast_at::<WireHeader>(1).unwrap();
+    let header = reader.read().unwrap();
+    assert_eq!((header.kind.get(), header.length.get()), (0x0102, 0x0304_0507));
+
+    assert_eq!(U16Be::new(u16::max_value()).checked_add(1), None);
+    assert_eq!(F32Le::from_bytes(1.5f32.to_bits().to_le_bytes()).get().to_bits(), 1.5f32.to_bits());
+}
+
+#[test]
+fn endian_integers_share_their_arithmetic() {
+    assert_eq!(U16Be::new(u16::max_value()).wrapping_add(2).get(), 1);
+    assert_eq!(U16Le::new(u16::max_value()).wrapping_add(2).get(), 1);
+    assert_eq!(U16Be::new(0).wrapping_sub(1).get(), u16::max_value());
+    assert_eq!(U16Le::new(0).wrapping_sub(1).get(), u16::max_value());
+
+    assert_eq!(U16Le::new(u16::max_value()).checked_add(1), None);
+    assert_eq!(U16Le::new(1).checked_sub(2), None);
+    assert_eq!(U16Be::new(5).checked_sub(2), Some(U16Be::new(3)));
+    assert_eq!(I32Be::new(i32::min_value()).checked_sub(1), None);
+    assert_eq!(I16Le::new(-3).checked_add(5), Some(I16Le::new(2)));
+
+    assert_eq!((I32Be::new(-7) + 10).get(), 3);
+    assert_eq!((I16Le::new(7) - 10).get(), -3);
+    assert_eq!((U32Le::new(40) + 2).to_bytes(), 42u32.to_le_bytes());
+    assert_eq!((U16Be::new(0x0102) - 1).to_bytes(), [0x01, 0x01]);
+}
+
+#[test]
+fn endian_floats_support_the_operators() {
+    assert_eq!((F32Le::new(1.5) + 2.0).get().to_bits(), 3.5f32.to_bits());
+    assert_eq!((F64Be::new(1.5) - 2.0).to_bytes(), (-0.5f64).to_bits().to_be_bytes());
+}
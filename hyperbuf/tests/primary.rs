@@ -6,24 +6,16 @@
 #[macro_use]
 extern crate hyperbuf_derive;
 
-use std::mem::size_of_val;
-use std::thread;
-use std::thread::JoinHandle;
-use std::time::{Duration, Instant};
-
-use bytes::{BigEndian, ByteOrder, LittleEndian};
-use bytes::BufMut;
-use futures::{Poll, TryFutureExt};
 use futures::executor::block_on;
 use parking_lot::Mutex;
 
-use hyperbuf::hypervec::{HyperVec, WriteVisitor};
+use hyperbuf::hypervec::HyperVec;
 
 use hyperbuf::impls::Castable;
-use hyperbuf::prelude::{ByteWrapper, BytePusher};
 use std::fmt::{Display, Formatter, Error};
-use std::marker::PhantomData;
 
+#[repr(C, packed)]
+#[derive(Copy, Clone, HyperPod)]
 pub struct Txx {
     field: u8,
     field2: u16,
@@ -31,14 +23,6 @@ pub struct Txx {
     field4: u16
 }
 
-fn zero_alloc(txx: &Txx) -> (*mut u8, usize) {
-    unsafe {
-        let layout = std::alloc::Layout::from_size_align_unchecked(9, 0);
-        (std::alloc::alloc(layout), 9)
-    }
-}
-
-
 impl Txx {
     pub fn new(seed: usize) -> Self {
         Self {field: (seed + 10) as u8, field2: (seed + 111) as u16, field3: (seed + 222) as u32, field4: seed as u16}
@@ -47,21 +31,12 @@ impl Txx {
 
 impl Display for Txx {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
-        write!(f, "{} {} {} -> SEED {}", self.field, self.field2, self.field3, self.field4)
-    }
-}
-
-fn print_all(txx: &Txx) {
-    let ptr = txx as *const Txx as *const u8;
-    let size = std::mem::size_of::<Txx>();
-
-    for x in 0..(size as isize) {
-        println!("TXX_VAL {}", unsafe { *ptr.offset(x) });
+        // The fields of a packed struct may not be borrowed, so they are copied out first
+        let (field, field2, field3, field4) = (self.field, self.field2, self.field3, self.field4);
+        write!(f, "{} {} {} -> SEED {}", field, field2, field3, field4)
     }
 }
 
-
-
 #[test]
 fn vectors(){
     let file_pos = "C:\\Users\\tbrau\\test.h";
@@ -77,25 +52,22 @@ fn vectors(){
     //wrapper.set_cursor_pos(3);
     //wrapper.push_u16s(items);
     println!("{}", wrapper);
-    let wrapper_ref = wrapper.as_static();
 
 
     /*for byte in wrapper_ref {
         println!("{}", unsafe {*byte});
     }*/
-    let write = wrapper_ref.cast_mut::<Txx>().unwrap();
+    let write = wrapper.cast_mut::<Txx>().unwrap();
     //let read = wrapper_ref.cast::<Txx>().unwrap();
-    block_on(write.visit(None, |write| unsafe {
+    block_on(write.visit(None, |write| {
         let m = write.write_array()?;
-        let mut i = 0;
 
         let v1 = &m[0];
         let v2 = &m[1];
         let v3 = &m[2];
         println!("RECOMBINE {} {} {}", v1, v2, v3);
-        for x in m {
+        for (i, x) in m.iter().enumerate() {
             println!("[{}] ptr: {}", i, x);
-            i+=1;
         }
         None
     })).unwrap();
@@ -118,13 +90,13 @@ fn vectors(){
 #[test]
 fn test_dynamic_memory() {
     let my_x: u16 = 100;
-    let mut wrapper = Mutex::new(my_x);
+    let wrapper = Mutex::new(my_x);
     for x in 0..u16::max_value() {
-        let _ = block_on(async {
+        block_on(async {
             *wrapper.lock() = x;
         });
 
-        let _ = block_on(async {
+        block_on(async {
             assert_eq!(*wrapper.lock(), x);
         });
     }
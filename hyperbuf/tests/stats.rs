@@ -0,0 +1,48 @@
+#![cfg(feature = "stats")]
+/*
+ * Copyright (c) 2019. The information/code/data contained within this file and all other files with the same copyright are protected under US Statutes. You must have explicit written access by Thomas P. Braun in order to access, view, modify, alter, or apply this code in any context commercial or non-commercial. If you have this code but were not given explicit written access by Thomas P. Braun, you must destroy the information herein for legal safety. You agree that if you apply the concepts herein without any written access, Thomas P. Braun will seek the maximum possible legal retribution.
+ */
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::Context;
+
+use futures::executor::block_on;
+use futures::task::noop_waker;
+
+use hyperbuf::hypervec::HyperVec;
+use hyperbuf::shared::SharedHyperVec;
+
+#[test]
+fn stats_count_tickets_waits_and_reallocations() {
+    let shared = SharedHyperVec::new(HyperVec::new_zeroed(4));
+    let writer = block_on(shared.write::<u32>()).unwrap();
+    let mut reader = shared.read::<u32>();
+    let waker = noop_waker();
+    assert!(Pin::new(&mut reader).poll(&mut Context::from_waker(&waker)).is_pending());
+
+    let stats = shared.stats();
+    assert_eq!(stats.tickets_issued, 2);
+    assert_eq!(stats.queue_depth, 1);
+    assert_eq!(stats.writes.admitted, 1);
+
+    drop(writer);
+    drop(block_on(reader).unwrap());
+
+    let mut hvec = shared.try_unwrap().unwrap();
+    hvec.extend(16);
+    let stats = hvec.stats();
+    assert_eq!(stats.tickets_completed, 2);
+    assert_eq!(stats.queue_depth, 0);
+    assert_eq!(stats.reads.admitted, 1);
+    assert!(stats.reads.max_wait <= stats.reads.total_wait);
+    assert_eq!(stats.reallocations, 1);
+}
+
+#[test]
+fn growing_a_view_counts_as_a_reallocation() {
+    let mut hvec = HyperVec::new_zeroed(8);
+    let mut tail = hvec.split_off(4);
+    tail.extend(16);
+    assert_eq!(tail.stats().reallocations, 1);
+}
@@ -0,0 +1,353 @@
+#![feature(async_await)]
+/*
+ * Copyright (c) 2019. The information/code/data contained within this file and all other files with the same copyright are protected under US Statutes. You must have explicit written access by Thomas P. Braun in order to access, view, modify, alter, or apply this code in any context commercial or non-commercial. If you have this code but were not given explicit written access by Thomas P. Braun, you must destroy the information herein for legal safety. You agree that if you apply the concepts herein without any written access, Thomas P. Braun will seek the maximum possible legal retribution.
+ */
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use futures::executor::block_on;
+use futures::task::ArcWake;
+
+use hyperbuf::hypervec::{HyperVec, SchedulingPolicy};
+use hyperbuf::impls::Castable;
+use hyperbuf::prelude::MemError;
+use hyperbuf::shared::SharedHyperVec;
+
+/// Counts how many times the task it belongs to was woken
+struct CountingWaker(AtomicUsize);
+
+impl ArcWake for CountingWaker {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        let _ = arc_self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+/// Polls `future` once with a waker which is never expected to be woken
+fn poll_once<F: Future + Unpin>(future: &mut F) -> Poll<F::Output> {
+    let waker = Arc::new(CountingWaker(AtomicUsize::new(0))).into_waker();
+    Pin::new(future).poll(&mut Context::from_waker(&waker))
+}
+
+/// Polls `future` once, and unwraps the guard it must already be able to hand out
+fn ready<F: Future<Output = Result<G, E>> + Unpin, G, E: std::fmt::Debug>(mut future: F) -> G {
+    match poll_once(&mut future) {
+        Poll::Ready(result) => result.unwrap(),
+        Poll::Pending => panic!("the ticket must already be admitted")
+    }
+}
+
+#[test]
+fn queued_visitor_is_woken() {
+    let shared = SharedHyperVec::new(HyperVec::new_zeroed(4));
+    let first = block_on(shared.write::<u32>()).unwrap();
+    let mut pending = shared.write::<u32>();
+
+    let counter = Arc::new(CountingWaker(AtomicUsize::new(0)));
+    let waker = counter.clone().into_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    assert!(Pin::new(&mut pending).poll(&mut cx).is_pending());
+    assert_eq!(counter.0.load(Ordering::SeqCst), 0);
+
+    drop(first);
+    assert_eq!(counter.0.load(Ordering::SeqCst), 1);
+    match Pin::new(&mut pending).poll(&mut cx) {
+        Poll::Ready(result) => assert!(result.is_ok()),
+        Poll::Pending => panic!("the second ticket must be ready once the first is released")
+    }
+}
+
+#[test]
+fn readers_share_an_epoch_and_writers_are_exclusive() {
+    let shared = SharedHyperVec::new(HyperVec::new_zeroed(4));
+    let first_reader = ready(shared.read::<u32>());
+    let second_reader = ready(shared.read::<u32>());
+    let mut writer = shared.write::<u32>();
+    let mut late_reader = shared.read::<u32>();
+
+    assert_eq!(*first_reader, 0);
+    assert_eq!(*second_reader, 0);
+    assert!(poll_once(&mut writer).is_pending());
+    assert!(poll_once(&mut late_reader).is_pending());
+
+    drop(first_reader);
+    assert!(poll_once(&mut writer).is_pending());
+    drop(second_reader);
+    let mut writer = ready(writer);
+    *writer = 7;
+    assert!(poll_once(&mut late_reader).is_pending());
+
+    drop(writer);
+    assert_eq!(*ready(late_reader), 7);
+}
+
+#[test]
+fn guards_hold_their_tickets_until_dropped() {
+    let mut hvec = HyperVec::new_zeroed(4);
+    block_on(async {
+        let mut guard = hvec.write_guard::<u32>().await.unwrap();
+        *guard = 5;
+        drop(guard);
+        assert_eq!(*hvec.read_guard::<u32>().await.unwrap(), 5);
+    });
+
+    let shared = SharedHyperVec::new(hvec);
+    let mut held = block_on(shared.write::<u32>()).unwrap();
+    let mut pending = shared.read::<u32>();
+
+    let counter = Arc::new(CountingWaker(AtomicUsize::new(0)));
+    let waker = counter.clone().into_waker();
+    let mut cx = Context::from_waker(&waker);
+    assert!(Pin::new(&mut pending).poll(&mut cx).is_pending());
+
+    *held += 1;
+    drop(held);
+    assert_eq!(counter.0.load(Ordering::SeqCst), 1);
+    match Pin::new(&mut pending).poll(&mut cx) {
+        Poll::Ready(guard) => assert_eq!(*guard.unwrap(), 6),
+        Poll::Pending => panic!("the reader must be admitted once the writer's guard drops")
+    }
+}
+
+#[test]
+fn shared_handles_cross_threads() {
+    fn assert_send<T: Send>(_: &T) {}
+
+    let shared = SharedHyperVec::new(HyperVec::new_zeroed(8));
+    let workers = (0..4).map(|_| {
+        let shared = shared.clone();
+        std::thread::spawn(move || {
+            let task = async move {
+                for _ in 0..100 {
+                    let mut guard = shared.write::<u64>().await.unwrap();
+                    *guard += 1;
+                }
+            };
+            assert_send(&task);
+            block_on(task)
+        })
+    }).collect::<Vec<_>>();
+
+    for worker in workers {
+        worker.join().unwrap();
+    }
+
+    let guard = block_on(shared.read::<u64>()).unwrap();
+    let reader = std::thread::spawn(move || *guard);
+    assert_eq!(reader.join().unwrap(), 400);
+    assert_eq!(shared.try_unwrap().unwrap().length(), 8);
+}
+
+#[test]
+fn cancelled_tickets_are_skipped_in_order() {
+    let shared = SharedHyperVec::new(HyperVec::new_zeroed(4));
+    let first = ready(shared.write::<u32>());
+    let cancelled_writer = shared.write::<u32>();
+    let cancelled_reader = shared.read::<u32>();
+    let mut last = shared.write::<u32>();
+
+    drop(cancelled_writer);
+    drop(cancelled_reader);
+    assert!(poll_once(&mut last).is_pending());
+
+    drop(first);
+    assert!(poll_once(&mut last).is_ready());
+}
+
+#[test]
+fn cancelled_writers_are_skipped_only_after_the_readers_ahead() {
+    let shared = SharedHyperVec::new(HyperVec::new_zeroed(4));
+    let counter = Arc::new(CountingWaker(AtomicUsize::new(0)));
+    let waker = counter.clone().into_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    let reader = block_on(shared.read::<u32>()).unwrap();
+    drop(shared.write::<u32>());
+    let mut next_reader = shared.read::<u32>();
+    let mut next_writer = shared.write::<u32>();
+    assert!(Pin::new(&mut next_reader).poll(&mut cx).is_pending());
+
+    drop(reader);
+    let next_reader = block_on(next_reader).unwrap();
+    assert!(Pin::new(&mut next_writer).poll(&mut cx).is_pending());
+    drop(next_reader);
+    *block_on(next_writer).unwrap() = 1;
+
+    // A writer which times out behind a reader is skipped the same way
+    let reader = block_on(shared.read::<u32>()).unwrap();
+    match shared.write_blocking_timeout::<u32>(Duration::from_millis(20)) {
+        Err(MemError::TIMED_OUT) => {},
+        _ => panic!("the writer must time out while the reader is held")
+    }
+
+    let mut next_reader = shared.read::<u32>();
+    assert!(Pin::new(&mut next_reader).poll(&mut cx).is_pending());
+    drop(reader);
+    assert_eq!(*block_on(next_reader).unwrap(), 1);
+    assert!(block_on(shared.write::<u32>()).is_ok());
+}
+
+#[test]
+fn visits_time_out_without_stalling_the_line() {
+    let shared = SharedHyperVec::new(HyperVec::new_zeroed(4));
+    let held = ready(shared.write::<u32>());
+
+    let started = Instant::now();
+    match shared.write_blocking_timeout::<u32>(Duration::from_millis(20)) {
+        Err(MemError::TIMED_OUT) => assert!(started.elapsed() >= Duration::from_millis(20)),
+        _ => panic!("the visit must time out while the first ticket is held")
+    }
+
+    let mut patient = shared.read::<u32>();
+    assert!(poll_once(&mut patient).is_pending());
+    drop(held);
+    assert_eq!(*ready(patient), 0);
+}
+
+#[test]
+fn consistent_reads_are_never_torn() {
+    let shared = SharedHyperVec::new(HyperVec::new_zeroed(16));
+    let writer = {
+        let shared = shared.clone();
+        std::thread::spawn(move || {
+            for value in 1..=10_000u64 {
+                let mut guard = block_on(shared.write::<[u64; 2]>()).unwrap();
+                *guard = [value, value];
+            }
+        })
+    };
+
+    let mut last = 0;
+    while last != 10_000 {
+        let [first, second] = shared.read_consistent::<[u64; 2]>();
+        assert_eq!(first, second);
+        assert!(first >= last);
+        last = first;
+    }
+
+    writer.join().unwrap();
+}
+
+#[test]
+fn consistent_reads_follow_reallocation() {
+    let mut hvec = HyperVec::new_zeroed(8);
+    hvec.as_mut_slice().copy_from_slice(&7u64.to_ne_bytes());
+    hvec.reserve(4096);
+    hvec.shrink_to_fit();
+    assert_eq!(hvec.read_consistent::<u64>(), 7);
+}
+
+#[test]
+fn blocking_visits_park_until_admitted() {
+    let shared = SharedHyperVec::new(HyperVec::new_zeroed(4));
+    let held = shared.write_blocking::<u32>().unwrap();
+    assert!(match shared.read_blocking_timeout::<u32>(Duration::from_millis(10)) {
+        Err(MemError::TIMED_OUT) => true,
+        _ => false
+    });
+
+    let reader = {
+        let shared = shared.clone();
+        std::thread::spawn(move || *shared.read_blocking::<u32>().unwrap())
+    };
+
+    std::thread::sleep(Duration::from_millis(20));
+    let mut held = held;
+    *held = 42;
+    drop(held);
+    assert_eq!(reader.join().unwrap(), 42);
+
+    let mut hvec = HyperVec::new_zeroed(4);
+    hvec.cast_mut::<u32>().unwrap().visit_blocking(None, |visitor| {
+        *visitor.write().unwrap() = 7;
+        None
+    }).unwrap();
+    assert_eq!(*hvec.read_blocking::<u32>().unwrap(), 7);
+}
+
+#[test]
+fn writer_preferring_policy_lets_writers_jump_readers() {
+    let shared = SharedHyperVec::new(HyperVec::new_zeroed(4).with_scheduling_policy(SchedulingPolicy::WriterPreferring));
+    let held = ready(shared.write::<u32>());
+    let mut reader = shared.read::<u32>();
+    let writer = shared.write::<u32>();
+    let second_reader = shared.read::<u32>();
+
+    drop(held);
+    assert!(poll_once(&mut reader).is_pending());
+    let mut writer = ready(writer);
+    *writer = 3;
+
+    drop(writer);
+    assert_eq!(*ready(reader), 3);
+    assert_eq!(*ready(second_reader), 3);
+}
+
+#[test]
+fn priority_lanes_admit_the_highest_lane_first() {
+    let shared = SharedHyperVec::new(HyperVec::new_zeroed(4).with_scheduling_policy(SchedulingPolicy::Priority { lanes: 3 }));
+    let held = ready(shared.write::<u32>());
+    let mut background = shared.write_with_priority::<u32>(0);
+    let control = shared.write_with_priority::<u32>(7);
+
+    drop(held);
+    assert!(poll_once(&mut background).is_pending());
+    let control = ready(control);
+
+    drop(control);
+    assert!(poll_once(&mut background).is_ready());
+}
+
+#[test]
+fn disjoint_range_visitors_run_together() {
+    let shared = SharedHyperVec::new(HyperVec::new_zeroed(16));
+    let held = block_on(shared.read::<u32>()).unwrap();
+    let mut low = shared.cast_range_mut::<u32, _>(0..4);
+    let high = shared.cast_range_mut::<u32, _>(8..12);
+    let mut overlapping = shared.cast_range::<[u16; 2], _>(2..6);
+    let mut whole = shared.read::<[u8; 16]>();
+
+    let counter = Arc::new(CountingWaker(AtomicUsize::new(0)));
+    let waker = counter.clone().into_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    assert!(Pin::new(&mut low).poll(&mut cx).is_pending());
+    drop(held);
+    let mut low = block_on(low).unwrap();
+    let mut high = block_on(high).unwrap();
+    *low = 1;
+    *high = 2;
+    assert!(Pin::new(&mut overlapping).poll(&mut cx).is_pending());
+
+    drop(low);
+    let overlapping = block_on(overlapping).unwrap();
+    assert!(Pin::new(&mut whole).poll(&mut cx).is_pending());
+
+    drop(high);
+    drop(overlapping);
+    let bytes = block_on(whole).unwrap();
+    assert_eq!(&bytes[0..4], &1u32.to_ne_bytes());
+    assert_eq!(&bytes[8..12], &2u32.to_ne_bytes());
+
+    match block_on(shared.cast_range::<u32, _>(12..20)) {
+        Err(MemError::OUT_OF_BOUNDS { offset, requested, len }) => assert_eq!((offset, requested, len), (12, 8, 16)),
+        _ => panic!("a range past the end of the buffer must be rejected")
+    }
+}
+
+#[test]
+fn range_visitors_cannot_extend_the_buffer() {
+    let mut hvec = HyperVec::new_zeroed(4);
+    let visitor = hvec.cast_range_mut::<u32, _>(0..4).unwrap();
+    match visitor.visit_blocking(Some(4), |_| None) {
+        Err(MemError::GENERIC(_)) => {},
+        _ => panic!("a range-scoped visitor must not be allowed to pre-allocate")
+    }
+
+    assert_eq!(hvec.length(), 4);
+}
@@ -12,16 +12,15 @@ variant_size_differences,
 */
 
 extern crate proc_macro;
-#[macro_use]
 extern crate quote;
 extern crate syn;
 
 use std::str::FromStr;
 
 use quote::quote;
-use syn::{DeriveInput, parse_macro_input};
-use syn::export::{Span, TokenStream};
-use std::ops::Add;
+use syn::{Data, DeriveInput, Meta, NestedMeta, Path, parse_macro_input};
+use syn::parse::{ParseStream, Parser};
+use syn::export::TokenStream;
 
 /*
 #[proc_macro_attribute]
@@ -58,7 +57,7 @@ pub fn expand(attr: TokenStream, item: TokenStream) -> TokenStream {
         panic!("Must specify depth");
     }
 
-    if !attr_str.contains(",") {
+    if !attr_str.contains(',') {
         panic!("Must specify type");
     }
 
@@ -76,7 +75,7 @@ pub fn expand(attr: TokenStream, item: TokenStream) -> TokenStream {
         panic!("Must contain a match statement with a variable accompanied by empty open and close brackets (hollow inside)");
     }
 
-    let parts = attr_str.split(",").collect::<Vec<&str>>();
+    let parts = attr_str.split(',').collect::<Vec<&str>>();
     let part0 = parts[0];
     let part1 = parts[1];
 
@@ -91,8 +90,6 @@ pub fn expand(attr: TokenStream, item: TokenStream) -> TokenStream {
     let line_expr = part1.as_str();
 
     let mut ret0 = String::new();
-    let mut store = Vec::<&str>::new();
-    let re = &mut store;
 
 
 
@@ -100,8 +97,8 @@ pub fn expand(attr: TokenStream, item: TokenStream) -> TokenStream {
         if line.contains("match") {
             ret0.push_str("match len {\n\n");
             // We inserted the match, now let's add the innards
-            let innards = (1..=depth).into_iter().map(move |idx| {
-                idx.to_string() + " => {\n" + (1..=idx).into_iter().map(|idx| {
+            let innards = (1..=depth).map(move |idx| {
+                idx.to_string() + " => {\n" + (1..=idx).map(|idx| {
                     let written_idx = idx - 1;
                     let written_str = written_idx.to_string();
                     let written_str = written_str.as_str();
@@ -161,4 +158,85 @@ fn impl_hello_macro(ast: &syn::DeriveInput) -> TokenStream {
         }
     };
     gen.into()
-}
\ No newline at end of file
+}
+
+/// Implements `hyperbuf::pod::HyperPod` for a struct, so that it may be cast from the bytes of a HyperVec. The struct must be
+/// `Copy`, `#[repr(C)]`, `#[repr(transparent)]` or `#[repr(packed)]`, and free of generic parameters. Every field must itself be
+/// HyperPod, and the fields must fill the struct without padding; both are asserted at compile time. A struct whose fields
+/// would be padded must either be `#[repr(C, packed)]`, or spell the padding out as fields of its own.
+///
+/// The impl refers to `::hyperbuf`. If the crate is renamed or re-exported, point the derive at it with
+/// `#[hyperpod(crate = "path::to::hyperbuf")]`
+#[proc_macro_derive(HyperPod, attributes(hyperpod))]
+pub fn hyper_pod(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    impl_hyper_pod(&ast)
+}
+
+fn impl_hyper_pod(ast: &DeriveInput) -> TokenStream {
+    let name = &ast.ident;
+    if !ast.generics.params.is_empty() {
+        return syn::Error::new_spanned(&ast.generics, "HyperPod cannot be derived for generic structs").to_compile_error().into();
+    }
+
+    let fields = match &ast.data {
+        Data::Struct(data) => data.fields.iter().map(|field| &field.ty).collect::<Vec<_>>(),
+        _ => return syn::Error::new_spanned(name, "HyperPod can only be derived for structs").to_compile_error().into()
+    };
+
+    // Without a defined layout, the compiler is free to reorder the fields and pad between them as it sees fit
+    let has_layout = ast.attrs.iter()
+        .filter(|attr| attr.path.is_ident("repr"))
+        .filter_map(|attr| attr.parse_meta().ok())
+        .any(|meta| match meta {
+            Meta::List(list) => list.nested.iter().any(|nested| match nested {
+                NestedMeta::Meta(meta) => ["C", "transparent", "packed"].iter().any(|repr| meta.path().is_ident(*repr)),
+                _ => false
+            }),
+            _ => false
+        });
+
+    if !has_layout {
+        return syn::Error::new_spanned(name, "HyperPod requires #[repr(C)], #[repr(transparent)] or #[repr(packed)]").to_compile_error().into();
+    }
+
+    let krate = match crate_path(ast) {
+        Ok(krate) => krate,
+        Err(err) => return err.to_compile_error().into()
+    };
+
+    let gen = quote! {
+        unsafe impl #krate::pod::HyperPod for #name {}
+
+        const _: () = {
+            #[allow(dead_code)]
+            fn assert_fields_are_pod() {
+                fn assert_pod<T: #krate::pod::HyperPod>() {}
+                #(assert_pod::<#fields>();)*
+            }
+
+            // Fails to compile (as an array length mismatch) if the struct is larger than its fields, i.e., holds padding
+            let _: [(); 0] = [(); ::std::mem::size_of::<#name>() - (0 #(+ ::std::mem::size_of::<#fields>())*)];
+        };
+    };
+    gen.into()
+}
+
+/// Returns the path given by `#[hyperpod(crate = "..")]`, or `::hyperbuf` if the attribute is absent
+fn crate_path(ast: &DeriveInput) -> syn::Result<Path> {
+    let attr = match ast.attrs.iter().find(|attr| attr.path.is_ident("hyperpod")) {
+        Some(attr) => attr,
+        None => return Ok(syn::parse_quote!(::hyperbuf))
+    };
+
+    // Parsed by hand, since `crate` is a keyword rather than an identifier and thus not valid within a Meta
+    let parser = |input: ParseStream| -> syn::Result<Path> {
+        let content;
+        let _ = syn::parenthesized!(content in input);
+        let _ = content.parse::<syn::Token![crate]>()?;
+        let _ = content.parse::<syn::Token![=]>()?;
+        content.parse::<syn::LitStr>()?.parse::<Path>()
+    };
+
+    parser.parse2(attr.tokens.clone())
+}
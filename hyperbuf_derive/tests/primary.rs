@@ -2,8 +2,17 @@
  * Copyright (c) 2019. The information/code/data contained within this file and all other files with the same copyright are protected under US Statutes. You must have explicit written access by Thomas P. Braun in order to access, view, modify, alter, or apply this code in any context commercial or non-commercial. If you have this code but were not given explicit written access by Thomas P. Braun, you must destroy the information herein for legal safety. You agree that if you apply the concepts herein without any written access, Thomas P. Braun will seek the maximum possible legal retribution.
  */
 
+#[macro_use]
+extern crate hyperbuf_derive;
+
+trait HelloMacro {
+    fn hello_macro(&self);
+}
+
+#[derive(HyxeAtom)]
+struct Primary;
+
 #[test]
-#[hyxe_atom]
 fn primary() {
-    println!("...");
-}
\ No newline at end of file
+    Primary.hello_macro();
+}